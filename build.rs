@@ -0,0 +1,63 @@
+/// Prepares the inputs that the `embed_input` feature compiles into the solutions.
+///
+/// Inputs are taken from the configured inputs directory (`paths.inputs` / `paths.data` in aoc.toml)
+/// and decrypted from their `.enc` counterpart if only that exists. They are written to `OUT_DIR`,
+/// so the `solution!` macro can embed them; days without an input get an empty file and
+/// exit with an error when run without `--input` or `--example`.
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+// the template modules only depend on the standard library, so they are shared with the crate.
+// their tests are compiled with `test_lib`, which leaves their imports unused here.
+#[allow(dead_code, unused_imports)]
+#[path = "src/template"]
+mod template {
+    pub mod config;
+    pub mod crypt;
+}
+
+use template::{config, crypt};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUT").is_none() {
+        return;
+    }
+
+    let inputs = config::get().data_dir("inputs");
+
+    println!("cargo:rerun-if-env-changed={}", config::CONFIG_ENV);
+    println!("cargo:rerun-if-env-changed={}", crypt::KEY_ENV);
+    println!(
+        "cargo:rerun-if-changed={}",
+        config::root().join(config::CONFIG_FILE).display()
+    );
+    println!(
+        "cargo:rerun-if-changed={}",
+        config::root().join(crypt::KEY_FILE).display()
+    );
+    println!("cargo:rerun-if-changed={}", inputs.display());
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("set by cargo")).join("inputs");
+    fs::create_dir_all(&out_dir).expect("could not create the directory for embedded inputs");
+
+    for day in 1..=25 {
+        let name = format!("{day:02}.txt");
+        let path = inputs.join(&name);
+
+        let input = match crypt::read_to_string(&path) {
+            Ok(input) => input,
+            Err(crypt::Error::IO(e)) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                println!(
+                    "cargo:warning=could not embed {}: {e}",
+                    config::display_path(&path)
+                );
+                String::new()
+            }
+        };
+
+        fs::write(out_dir.join(name), input).expect("could not write embedded input");
+    }
+}
@@ -39,8 +39,7 @@ fn parse_line_text(line: &str) -> u32 {
     if let Some(first_digit) = line
         .chars()
         .enumerate()
-        .filter(|(_i, c)| c.is_ascii_digit())
-        .next()
+        .find(|(_i, c)| c.is_ascii_digit())
         .map(|(i, d)| NumMatch {
             start: i,
             num: d.to_string().parse::<u8>().unwrap(),
@@ -105,6 +104,23 @@ fn parse_line_num(line: &str) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::fuzz::{self, Rng};
+
+    /// Lines of digits and letters that can't spell a number word.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let alphabet = [
+            'a', 'b', 'c', 'd', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+        ];
+        (0..size)
+            .map(|_| {
+                let len = rng.between(1, 12);
+                let mut line: String = (0..len).map(|_| *rng.choose(&alphabet)).collect();
+                line.push(*rng.choose(&alphabet[4..]));
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
     #[test]
     fn test_part_one() {
@@ -119,4 +135,13 @@ mod tests {
         ));
         assert_eq!(result, Some(281));
     }
+
+    #[test]
+    fn fuzz_part_two_without_words() {
+        let config = fuzz::Config {
+            iterations: 300,
+            ..Default::default()
+        };
+        fuzz::check_equal(DAY, &config, generate, part_one, part_two);
+    }
 }
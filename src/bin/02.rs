@@ -1,6 +1,8 @@
 advent_of_code::solution!(2);
-use std::iter::Sum;
-use std::ops::Add;
+use advent_of_code::scan;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
 #[derive(Debug, Default, Copy, Clone)]
 struct Set {
@@ -8,22 +10,6 @@ struct Set {
     green: u8,
     blue: u8,
 }
-impl Add for Set {
-    type Output = Set;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            red: self.red + rhs.red,
-            green: self.green + rhs.green,
-            blue: self.blue + rhs.blue,
-        }
-    }
-}
-impl Sum for Set {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Self::default(), |sum, s| sum + s)
-    }
-}
 #[derive(Debug)]
 struct Game {
     num: u8,
@@ -39,69 +25,86 @@ impl Game {
         true
     }
 }
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
     let max = Set {
         red: 12,
         green: 13,
         blue: 14,
     };
-    let lines = input.trim_end().split('\n');
-    let games = lines
-        .map(|l| parse_line(l))
-        .filter(|g| g.is_valid(max))
-        .map(|g| g.num as u32)
-        .sum();
-    Some(games)
+    let mut sum = 0;
+    for line in input.trim_end().split('\n') {
+        let game = parse_line(line)?;
+        if game.is_valid(max) {
+            sum += game.num as u32;
+        }
+    }
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let lines = input.trim_end().split('\n');
-    let sum = lines
-        .map(|l| parse_line(l))
-        .map(|game| {
-            let max_red = game.sets.iter().map(|s| s.red).max().unwrap();
-            let max_green = game.sets.iter().map(|s| s.green).max().unwrap();
-            let max_blue = game.sets.iter().map(|s| s.blue).max().unwrap();
-            let power = max_red as u32 * max_green as u32 * max_blue as u32;
-            power
-        })
-        .sum();
-    Some(sum)
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let mut sum = 0;
+    for line in input.trim_end().split('\n') {
+        let game = parse_line(line)?;
+        let max_red = game.sets.iter().map(|s| s.red).max().unwrap_or(0);
+        let max_green = game.sets.iter().map(|s| s.green).max().unwrap_or(0);
+        let max_blue = game.sets.iter().map(|s| s.blue).max().unwrap_or(0);
+
+        sum += max_red as u32 * max_green as u32 * max_blue as u32;
+    }
+    Ok(sum)
 }
 
-fn parse_line(line: &str) -> Game {
-    let mut pieces = line.split(':');
-    let num: u8 = pieces
-        .next()
-        .unwrap()
-        .split(' ')
-        .skip(1)
-        .next()
-        .unwrap()
-        .parse()
-        .unwrap();
-    let game_details = pieces.next().unwrap();
-    let sets = parse_details(game_details);
-    Game { num, sets }
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    Scan(scan::Error),
+    InvalidColor(String),
 }
 
-fn parse_details(details: &str) -> Vec<Set> {
-    details.split(';').map(|s| parse_set(s)).collect()
+impl From<scan::Error> for ParseError {
+    fn from(e: scan::Error) -> Self {
+        ParseError::Scan(e)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Scan(e) => write!(f, "{e}"),
+            ParseError::InvalidColor(s) => write!(f, "invalid color \"{s}\""),
+        }
+    }
 }
-fn parse_set(set: &str) -> Set {
-    let elements = set.split(',').map(|s| s.trim()).map(|s| {
-        let mut set = Set::default();
-        let mut pieces = s.split(' ');
-        let num = pieces.next().unwrap().parse::<u8>().unwrap();
-        match pieces.next().unwrap() {
-            "red" => set.red += num,
-            "green" => set.green += num,
-            "blue" => set.blue += num,
-            s => panic!("Invalid color: {s}"),
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Scan(e) => e.source(),
+            ParseError::InvalidColor(_) => None,
         }
-        set
-    });
-    elements.sum()
+    }
+}
+
+fn parse_line(line: &str) -> Result<Game, ParseError> {
+    let (num, sets) = scan!(line, "Game {}: {}" => u8, [Set; ";"])?;
+    Ok(Game { num, sets })
+}
+
+impl FromStr for Set {
+    type Err = ParseError;
+
+    fn from_str(set: &str) -> Result<Self, Self::Err> {
+        let mut sum = Set::default();
+        for element in set.split(',').map(|s| s.trim()) {
+            let (num, color) = scan!(element, "{} {}" => u8, String)?;
+            match color.as_str() {
+                "red" => sum.red += num,
+                "green" => sum.green += num,
+                "blue" => sum.blue += num,
+                _ => return Err(ParseError::InvalidColor(color)),
+            }
+        }
+        Ok(sum)
+    }
 }
 
 #[cfg(test)]
@@ -111,12 +114,41 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        assert_eq!(result, Ok(8));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        assert_eq!(result, Ok(2286));
+    }
+
+    #[test]
+    fn test_trailing_blank_line() {
+        let input = advent_of_code::template::read_file("examples", DAY) + "\n";
+        assert_eq!(part_one(&input), Ok(8));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let render = |e: ParseError| advent_of_code::template::runner::render_error(&e);
+
+        let error = part_one("Game 1: 3 purple").unwrap_err();
+        assert_eq!(
+            render(error),
+            "failed to scan \"Game 1: 3 purple\": invalid Set \"3 purple\"\n  caused by: invalid color \"purple\""
+        );
+
+        let error = part_one("Game 1: 3").unwrap_err();
+        assert_eq!(
+            render(error),
+            "failed to scan \"Game 1: 3\": invalid Set \"3\"\n  caused by: failed to scan \"3\": expected \" \""
+        );
+
+        let error = part_two("Game x: 3 red").unwrap_err();
+        assert_eq!(
+            render(error),
+            "failed to scan \"Game x: 3 red\": invalid u8 \"x\"\n  caused by: invalid digit found in string"
+        );
     }
 }
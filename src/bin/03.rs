@@ -1,7 +1,9 @@
+use advent_of_code::grid::Grid;
+use advent_of_code::visualize::{self, Cell, Recorder, Rgb};
 use std::collections::HashMap;
 use std::fmt;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, stress: scale_input, visualize: visualize_engine);
 
 enum Cardinal {
     NorthWest,
@@ -192,12 +194,71 @@ impl fmt::Debug for Engine {
                 let val = self.map.get(&(row, col)).unwrap();
                 write!(f, "{val:?} ")?;
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
+/// Stacks `scale` copies of the schematic on top of each other.
+fn scale_input(input: &str, scale: u32) -> String {
+    vec![input.trim_end(); scale as usize].join("\n")
+}
+
+/// Shows part numbers next to a symbol in green, all other numbers in red and
+/// gears (`*` next to exactly two part numbers) highlighted in magenta.
+fn visualize_engine(input: &str) {
+    let engine = Engine::parse(input);
+    let mut valid = HashMap::new();
+    for row in 0..engine.rows {
+        let mut run: Vec<(isize, isize)> = vec![];
+        for col in 0..=engine.cols {
+            let loc = (row, col);
+            match engine.map.get(&loc) {
+                Some(el) if el.is_part() => run.push(loc),
+                _ => {
+                    let is_valid = run.iter().any(|l| engine.has_symbol(*l));
+                    valid.extend(run.drain(..).map(|l| (l, is_valid)));
+                }
+            }
+        }
+    }
+
+    let frame = Grid::parse(input, Cell::new).map(|(x, y), cell| {
+        let loc = (y as isize, x as isize);
+        match engine.map.get(&loc) {
+            Some(el) if el.is_gear() => {
+                let mut adj: Vec<u32> = engine
+                    .adjacent(loc)
+                    .iter()
+                    .filter_map(|e| e.part())
+                    .collect();
+                adj.dedup();
+                if adj.len() == 2 {
+                    cell.fg(Rgb::BLACK).bg(Rgb::MAGENTA)
+                } else {
+                    cell.fg(Rgb::YELLOW)
+                }
+            }
+            Some(el) if el.is_symbol() => cell.fg(Rgb::YELLOW),
+            Some(el) if el.is_part() => match valid.get(&loc) {
+                Some(true) => cell.fg(Rgb::GREEN),
+                _ => cell.fg(Rgb::RED),
+            },
+            _ => *cell,
+        }
+    });
+
+    visualize::show(&frame);
+
+    let mut recorder = Recorder::new();
+    recorder.push(frame);
+    match recorder.save_png("03", 8) {
+        Ok(dir) => println!("Saved visualization to {dir:?}"),
+        Err(e) => eprintln!("Failed to save visualization: {e}"),
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let engine = Engine::parse(input);
     // TODO: make iterator?
@@ -241,7 +302,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                     .collect();
                 adj.dedup(); // problematic?
                 if adj.len() == 2 {
-                    let power = adj.iter().fold(1u32, |power, el| el * power);
+                    let power = adj.iter().product::<u32>();
                     return Some(power);
                 }
             }
@@ -266,4 +327,11 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(467835));
     }
+
+    #[test]
+    fn test_scale_input() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let result = part_one(&scale_input(&input, 4));
+        assert_eq!(result, Some(4361 * 4));
+    }
 }
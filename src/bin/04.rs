@@ -1,3 +1,4 @@
+use advent_of_code::scan;
 use std::collections::BTreeSet;
 
 advent_of_code::solution!(4);
@@ -10,28 +11,14 @@ struct Card {
     copies: u32,
 }
 impl Card {
-    pub fn parse(line: &str) -> Self {
-        let mut data = line.split(':');
-        let num = data
-            .next()
-            .unwrap()
-            .split(' ')
-            .filter(|p| !p.is_empty())
-            .skip(1)
-            .next()
-            .unwrap()
-            .parse::<u32>()
-            .unwrap();
-
-        let mut numbers = data.next().unwrap().split(" | ");
-        let winning = parse_numbers(numbers.next().unwrap());
-        let numbers = parse_numbers(numbers.next().unwrap());
-        Self {
+    pub fn parse(line: &str) -> Result<Self, scan::Error> {
+        let (num, winning, numbers) = scan!(line, "Card {}: {} | {}" => u32, [u32], [u32])?;
+        Ok(Self {
             num,
-            winning,
-            numbers,
+            winning: winning.into_iter().collect(),
+            numbers: numbers.into_iter().collect(),
             copies: 1,
-        }
+        })
     }
     pub fn points(&self) -> u32 {
         let matches = self.wins() as u32;
@@ -44,27 +31,20 @@ impl Card {
         self.winning.intersection(&self.numbers).count()
     }
 }
-fn parse_numbers(nums: &str) -> BTreeSet<u32> {
-    nums.trim()
-        .split(' ')
-        .filter(|s| !s.is_empty())
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect()
+pub fn part_one(input: &str) -> Result<u32, scan::Error> {
+    let mut sum = 0;
+    for line in input.trim_end().lines() {
+        sum += Card::parse(line)?.points();
+    }
+    Ok(sum)
 }
-pub fn part_one(input: &str) -> Option<u32> {
-    let sum = input
+
+pub fn part_two(input: &str) -> Result<u32, scan::Error> {
+    let mut cards = input
         .trim_end()
         .lines()
-        .map(|l| {
-            let card = Card::parse(l);
-            card.points()
-        })
-        .sum();
-    Some(sum)
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut cards: Vec<Card> = input.trim_end().lines().map(|l| Card::parse(l)).collect();
+        .map(Card::parse)
+        .collect::<Result<Vec<_>, _>>()?;
     for i in 0..cards.len() {
         let (num, copies, wins) = {
             let card = &cards[i];
@@ -73,10 +53,10 @@ pub fn part_two(input: &str) -> Option<u32> {
         };
         let new_cards = cards.iter_mut().skip(num as _).take(wins);
         for copy in new_cards {
-            copy.copies += copies * 1;
+            copy.copies += copies;
         }
     }
-    Some(cards.iter().map(|c| c.copies).sum())
+    Ok(cards.iter().map(|c| c.copies).sum())
 }
 
 #[cfg(test)]
@@ -86,12 +66,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+        assert_eq!(result, Ok(13));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+        assert_eq!(result, Ok(30));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let error = part_one("Card 1: 41 48 83").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to scan \"Card 1: 41 48 83\": expected \" | \""
+        );
     }
 }
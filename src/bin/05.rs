@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::ops::Range;
 use std::str::FromStr;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, part_two: [part_two_brute_force], stress: scale_input);
 
 #[derive(Debug, Hash, Eq, PartialEq, Copy, Clone)]
 pub enum MapType {
@@ -61,6 +61,23 @@ impl SectionMap {
             .unwrap_or(value);
         result
     }
+    /// Maps every value of `ranges`, splitting them where they cross the bounds of a [`SeedMap`].
+    fn map_ranges(&self, ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        let mut mapped = vec![];
+        let mut unmapped = ranges;
+        for map in &self.maps {
+            let mut rest = vec![];
+            for range in unmapped {
+                let (inside, outside) = map.split(range);
+                mapped.extend(inside);
+                rest.extend(outside);
+            }
+            unmapped = rest;
+        }
+        // values outside of every map keep their number.
+        mapped.extend(unmapped);
+        mapped
+    }
 }
 #[derive(Debug)]
 struct SeedMap {
@@ -85,10 +102,25 @@ impl SeedMap {
         }
         Some((value as i64 - self.offset) as u64)
     }
+    /// Splits `range` into its mapped part inside the source and the unmapped parts before and after it.
+    fn split(&self, range: Range<u64>) -> (Option<Range<u64>>, Vec<Range<u64>>) {
+        let start = range.start.max(self.source.start);
+        let end = range.end.min(self.source.end);
+        if start >= end {
+            return (None, vec![range]);
+        }
+
+        let inside = (start as i64 - self.offset) as u64..(end as i64 - self.offset) as u64;
+        let outside = [range.start..start, end..range.end]
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .collect();
+        (Some(inside), outside)
+    }
 }
 
 fn parse_seeds(line: &str) -> Vec<u64> {
-    (&line[6..])
+    line[6..]
         .trim()
         .split(' ')
         .map(|n| n.parse::<u64>().unwrap())
@@ -100,7 +132,7 @@ fn parse_section(section: &str) -> (MapType, SectionMap) {
     let name_end = name.find(' ').unwrap();
     let section_name = &name[0..name_end];
     // println!("Section name: {section_name}");
-    let maps = lines.map(|l| SeedMap::parse(l)).collect();
+    let maps = lines.map(SeedMap::parse).collect();
     let map_type = MapType::from_str(section_name).unwrap();
     let section = SectionMap {
         _name: section_name.to_string(),
@@ -134,9 +166,23 @@ impl Data {
     }
 }
 
+/// Widens every seed range by `scale`, the maps stay the same.
+fn scale_input(input: &str, scale: u32) -> String {
+    let (seeds, maps) = input.split_once("\n\n").unwrap();
+    let seeds: Vec<String> = parse_seeds(seeds)
+        .chunks(2)
+        .map(|chunk| format!("{} {}", chunk[0], chunk[1] * u64::from(scale)))
+        .collect();
+    format!("seeds: {}\n\n{maps}", seeds.join(" "))
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
     // answer: 240320250
-    let data = Data::parse(input);
+    let data = {
+        let _span = advent_of_code::span!("parse");
+        Data::parse(input)
+    };
+    let _span = advent_of_code::span!("locate");
     let lowest = data
         .seeds
         .into_iter()
@@ -148,12 +194,36 @@ pub fn part_one(input: &str) -> Option<u64> {
 
 pub fn part_two(input: &str) -> Option<u64> {
     // answer: 28580589
-    let data = Data::parse(input);
-    let seeds: Vec<u64> = data
+    let data = {
+        let _span = advent_of_code::span!("parse");
+        Data::parse(input)
+    };
+    let _span = advent_of_code::span!("locate");
+    let seeds = data
         .seeds
         .chunks(2)
-        .flat_map(|chunk| (chunk[0]..(chunk[0] + chunk[1])).collect::<Vec<u64>>())
+        .map(|chunk| chunk[0]..(chunk[0] + chunk[1]))
         .collect();
+    let locations = MapType::all().iter().fold(seeds, |ranges, map_type| {
+        data.maps.get(map_type).unwrap().map_ranges(ranges)
+    });
+    locations.into_iter().map(|r| r.start).min()
+}
+
+/// Locates every single seed, kept to cross-check [`part_two`] with `--compare`.
+pub fn part_two_brute_force(input: &str) -> Option<u64> {
+    let data = {
+        let _span = advent_of_code::span!("parse");
+        Data::parse(input)
+    };
+    let seeds: Vec<u64> = {
+        let _span = advent_of_code::span!("expand seeds");
+        data.seeds
+            .chunks(2)
+            .flat_map(|chunk| (chunk[0]..(chunk[0] + chunk[1])).collect::<Vec<u64>>())
+            .collect()
+    };
+    let _span = advent_of_code::span!("locate");
     let lowest = seeds
         .par_iter()
         .map(|s| sequence(*s, MapType::all(), &data.maps))
@@ -177,4 +247,23 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
     }
+
+    #[test]
+    fn test_part_two_brute_force() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(part_two_brute_force(&input), Some(46));
+        // seed ranges crossing and containing map bounds, checked against every single seed.
+        let input = input.replacen("seeds: 79 14 55 13", "seeds: 40 70 0 120 97 2", 1);
+        assert_eq!(part_two(&input), part_two_brute_force(&input));
+    }
+
+    #[test]
+    fn test_scale_input() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let scaled = scale_input(&input, 3);
+        assert!(scaled.starts_with("seeds: 79 42 55 39\n\nseed-to-soil map:"));
+        // the range 55..68 is widened to 55..94 and reaches seed 70, which maps to location 0.
+        assert_eq!(part_two(&scaled), Some(0));
+        assert_eq!(part_two_brute_force(&scaled), Some(0));
+    }
 }
@@ -1,23 +1,11 @@
 advent_of_code::solution!(6);
+use advent_of_code::{math, scan};
 
 // Charge time: 1mm per 1ms
 #[derive(Debug, Copy, Clone)]
 struct Time(u64); // ms
 #[derive(Debug, Copy, Clone)]
-struct Speed(u64); // mm per ms
-#[derive(Debug, Copy, Clone)]
 struct Distance(u64); // mm
-impl Time {
-    pub fn charge_to_speed(&self) -> Speed {
-        // since it's 1mm per 1ms of charge, speed is same as time, just diff units
-        Speed(self.0)
-    }
-}
-impl Speed {
-    pub fn distance_for_speed(&self, time: Time) -> Distance {
-        Distance(time.0 * self.0)
-    }
-}
 #[derive(Debug)]
 struct Race {
     /// Farthest distance a boat went in this race
@@ -32,90 +20,43 @@ impl Race {
             time: Time(time),
         }
     }
-    // pub fn possible_charge_times(&self) -> Vec<Time> {
-    //     let time_range = 1..(self.time.0 - 1); // all but 0 and max time
-    //     time_range.map(|t| Time(t)).collect()
-    // }
-    pub fn attempts(&self) -> Vec<RaceAttempt> {
-        let time_range = 1..self.time.0; // all but 0 and max time
-        time_range
-            .map(|t| {
-                let charge = Time(t);
-                let distance = Time(self.time.0 - t);
-                RaceAttempt { charge, distance }
-            })
-            .collect()
-    }
+    /// Number of charge times that beat the record.
+    ///
+    /// Charging for `c` ms travels `c·(time − c)` mm, so the winning charge times are the integers
+    /// with `c² − time·c + record < 0`, strictly between the roots of the quadratic.
     pub fn possible_wins(&self) -> usize {
-        self.attempts()
-            .into_iter()
-            .map(|a| a.distance())
-            .filter(|a| a.0 > self.record.0)
-            .count()
-    }
-}
-/// Describes an attempt split between how long charge & how long for travel
-#[derive(Debug)]
-struct RaceAttempt {
-    /// Time button held for charging
-    charge: Time,
-    /// Time left spent traveling
-    distance: Time,
-}
-impl RaceAttempt {
-    pub fn distance(&self) -> Distance {
-        let speed = self.charge.charge_to_speed();
-        speed.distance_for_speed(self.distance)
+        let time = i64::try_from(self.time.0).expect("race time fits in i64");
+        let record = i64::try_from(self.record.0).expect("record fits in i64");
+        math::quadratic_below(1, -time, record).map_or(0, |wins| wins.count())
     }
 }
 
-fn parse_numbers(line: &str) -> Vec<u64> {
-    let colon = line.find(':').unwrap();
-    (&line[colon..])
-        .trim()
-        .split(' ')
-        .filter_map(|n| n.parse::<u64>().ok())
-        .collect()
-}
-fn parse(input: &str) -> Vec<Race> {
+fn parse(input: &str) -> Result<Vec<Race>, scan::Error> {
     let mut lines = input.lines();
-    let time = parse_numbers(lines.next().unwrap());
-    let distance = parse_numbers(lines.next().unwrap());
-    time.into_iter()
-        .zip(distance.into_iter())
+    let (time,) = scan!(lines.next().unwrap_or_default(), "Time: {}" => [u64])?;
+    let (distance,) = scan!(lines.next().unwrap_or_default(), "Distance: {}" => [u64])?;
+    Ok(time
+        .into_iter()
+        .zip(distance)
         .map(|(t, d)| Race::new(d, t))
-        .collect()
+        .collect())
 }
-pub fn part_one(input: &str) -> Option<u32> {
-    let races = parse(input);
-    // println!("Races: {races:?}");
+pub fn part_one(input: &str) -> Result<u32, scan::Error> {
+    let races = parse(input)?;
     let mut total = 1;
     for race in races {
         let wins = race.possible_wins();
         total *= wins;
     }
-    Some(total as _)
+    Ok(total as _)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> Result<u64, scan::Error> {
+    // the numbers of part two are split by spaces, which are ignored.
     let input = input.replace(' ', "");
     let mut lines = input.lines();
-    let time = lines
-        .next()
-        .unwrap()
-        .split(':')
-        .nth(1)
-        .unwrap()
-        .parse::<u64>()
-        .unwrap();
-    let distance = lines
-        .next()
-        .unwrap()
-        .split(':')
-        .nth(1)
-        .unwrap()
-        .parse::<u64>()
-        .unwrap();
+    let (time,) = scan!(lines.next().unwrap_or_default(), "Time:{}" => u64)?;
+    let (distance,) = scan!(lines.next().unwrap_or_default(), "Distance:{}" => u64)?;
 
     let race = Race {
         record: Distance(distance),
@@ -123,8 +64,7 @@ pub fn part_two(input: &str) -> Option<u64> {
     };
     let wins = race.possible_wins();
 
-    // println!("s: {time:?} {distance:?}");
-    Some(wins as _)
+    Ok(wins as _)
 }
 
 #[cfg(test)]
@@ -134,12 +74,32 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+        assert_eq!(result, Ok(288));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
+        assert_eq!(result, Ok(71503));
+    }
+
+    #[test]
+    fn test_possible_wins() {
+        // charging 10 or 20 ms exactly ties the record, so they don't win.
+        assert_eq!(Race::new(200, 30).possible_wins(), 9);
+        assert_eq!(Race::new(8, 6).possible_wins(), 1);
+        assert_eq!(Race::new(9, 7).possible_wins(), 4);
+        // the best charge time only ties the record.
+        assert_eq!(Race::new(16, 8).possible_wins(), 0);
+        assert_eq!(Race::new(0, 1).possible_wins(), 0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let error = part_one("Time: 7 15\nDistance: 9 x").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to scan \"Distance: 9 x\": invalid u64 \"x\""
+        );
     }
 }
@@ -114,6 +114,134 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days, e.g. for batch operations of commands.
+///
+/// Parsed from a comma-separated list of days and ranges, where `latest` is the most recently
+/// unlocked day of `AOC_YEAR`:
+///
+/// ```
+/// # use advent_of_code::DaySelection;
+/// let days: DaySelection = "1..=3,5,..2".parse().unwrap();
+/// assert_eq!(days.iter().map(|d| d.into_inner()).collect::<Vec<_>>(), vec![1, 2, 3, 5]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection(Vec<Day>);
+
+impl DaySelection {
+    /// Creates a selection of every day.
+    #[must_use]
+    pub fn all() -> Self {
+        Self(all_days().collect())
+    }
+
+    /// Iterates over the selected days in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        self.0.iter().copied()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the day if exactly one day is selected.
+    #[must_use]
+    pub fn single(&self) -> Option<Day> {
+        match self.0.as_slice() {
+            [day] => Some(*day),
+            _ => None,
+        }
+    }
+
+    fn parse_item(item: &str) -> Result<Vec<Day>, DaySelectionFromStrError> {
+        let error = || DaySelectionFromStrError(item.to_string());
+        let bound = |s: &str, default: u8| -> Result<u8, DaySelectionFromStrError> {
+            if s.is_empty() {
+                Ok(default)
+            } else {
+                s.parse::<Day>().map(Day::into_inner).map_err(|_| error())
+            }
+        };
+
+        if item == "latest" {
+            let year = crate::template::aoc_cli::get_year().ok_or_else(error)?;
+            let day =
+                crate::template::calendar::latest_unlocked(year, crate::template::calendar::now());
+            return day.map(|day| vec![day]).ok_or_else(error);
+        }
+
+        let (start, end) = if let Some((start, end)) = item.split_once("..=") {
+            (bound(start, 1)?, bound(end, 25)?)
+        } else if let Some((start, end)) = item.split_once("..") {
+            // exclusive ranges need an end to exclude, `..` alone is every day.
+            let end = if end.is_empty() { 26 } else { bound(end, 25)? };
+            (bound(start, 1)?, end.checked_sub(1).ok_or_else(error)?)
+        } else {
+            let day = bound(item, 0)?;
+            (day, day)
+        };
+
+        if start > end {
+            return Err(error());
+        }
+
+        Ok((start..=end).filter_map(Day::new).collect())
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = vec![];
+        for item in s.split(',').map(str::trim) {
+            // stray commas like `5,` or `5,,6` are most likely typos.
+            if item.is_empty() {
+                return Err(DaySelectionFromStrError(s.to_string()));
+            }
+            days.extend(Self::parse_item(item)?);
+        }
+
+        days.sort();
+        days.dedup();
+
+        if days.is_empty() {
+            return Err(DaySelectionFromStrError(s.to_string()));
+        }
+
+        Ok(Self(days))
+    }
+}
+
+impl From<Day> for DaySelection {
+    fn from(day: Day) -> Self {
+        Self(vec![day])
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub struct DaySelectionFromStrError(String);
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection \"{}\", expecting days between 1 and 25 like `5`, `1..=6`, `3,5,9`, `..10` or `latest`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +262,32 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySelection};
+
+    fn selection(s: &str) -> Option<Vec<u8>> {
+        let days: DaySelection = s.parse().ok()?;
+        Some(days.iter().map(Day::into_inner).collect())
+    }
+
+    #[test]
+    fn parses_selections() {
+        assert_eq!(selection("5"), Some(vec![5]));
+        assert_eq!(selection("1..=3"), Some(vec![1, 2, 3]));
+        assert_eq!(selection("1..3"), Some(vec![1, 2]));
+        assert_eq!(selection("..4"), Some(vec![1, 2, 3]));
+        assert_eq!(selection("23.."), Some(vec![23, 24, 25]));
+        assert_eq!(selection("9, 3,5,3"), Some(vec![3, 5, 9]));
+        assert_eq!(selection("..").map(|d| d.len()), Some(25));
+        assert_eq!(selection("0"), None);
+        assert_eq!(selection("5..=2"), None);
+        assert_eq!(selection("1..1"), None);
+        assert_eq!(selection("1..=26"), None);
+        assert_eq!(selection("x"), None);
+        assert_eq!(selection(""), None);
+        assert_eq!(selection("5,"), None);
+        assert_eq!(selection("5,,6"), None);
+        assert_eq!(selection(",5"), None);
+    }
 
     #[test]
     fn all_days_iterator() {
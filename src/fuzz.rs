@@ -0,0 +1,396 @@
+//! Differential fuzzing and property checks for solutions.
+//!
+//! A day supplies an input generator and the harness runs either two implementations
+//! ([`check_equal`]) or an implementation and an invariant ([`check_invariant`]) on many random inputs.
+//! Failing inputs are shrunk line by line and number by number, and the minimal input is written
+//! to `data/examples/<day>-fuzz.txt` so it can be turned into a regular example test.
+//!
+//! ```
+//! // in the `tests` module of a day:
+//! fn fuzz_part_two() {
+//!     fuzz::check_equal(DAY, &fuzz::Config::default(), generate, part_two, part_two_fast);
+//! }
+//! ```
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::{env, fs};
+
+use crate::template::config;
+use crate::Day;
+
+/// A small, seeded pseudo random number generator (`SplitMix64`).
+/// It is not cryptographically secure, but fast and reproducible across platforms.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..bound`. Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        // rejection sampling avoids the modulo bias for large bounds.
+        let zone = u64::MAX - (u64::MAX % bound);
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Returns a value in the inclusive range `min..=max`.
+    pub fn between(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "min must not be greater than max");
+        match (max - min).checked_add(1) {
+            Some(bound) => min + self.below(bound),
+            None => self.next_u64(),
+        }
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let value = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        value < probability
+    }
+
+    /// Returns a random element of a non-empty slice.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        #[allow(clippy::cast_possible_truncation)]
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// Settings for a fuzzing run.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Number of random inputs to check.
+    pub iterations: usize,
+    /// Seed of the random number generator. Override with the `AOC_FUZZ_SEED` env variable.
+    pub seed: u64,
+    /// The size hint passed to the generator grows from 1 to this value over the run.
+    pub max_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            iterations: 1000,
+            seed: 2023,
+            max_size: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Failure {
+    Panic(String),
+    Violation(String),
+}
+
+impl Failure {
+    fn same_kind(&self, other: &Failure) -> bool {
+        matches!(
+            (self, other),
+            (Failure::Panic(_), Failure::Panic(_)) | (Failure::Violation(_), Failure::Violation(_))
+        )
+    }
+}
+
+/// Runs two implementations on random inputs and panics with a shrunk input if their results differ.
+pub fn check_equal<T: PartialEq + Debug>(
+    day: Day,
+    config: &Config,
+    generate: impl Fn(&mut Rng, usize) -> String,
+    a: impl Fn(&str) -> T,
+    b: impl Fn(&str) -> T,
+) {
+    check_invariant(day, config, generate, |input| {
+        let (left, right) = (a(input), b(input));
+        if left == right {
+            Ok(())
+        } else {
+            Err(format!("implementations disagree: {left:?} != {right:?}"))
+        }
+    });
+}
+
+/// Checks an invariant on random inputs and panics with a shrunk input if it does not hold.
+pub fn check_invariant(
+    day: Day,
+    config: &Config,
+    generate: impl Fn(&mut Rng, usize) -> String,
+    invariant: impl Fn(&str) -> Result<(), String>,
+) {
+    let seed = env::var("AOC_FUZZ_SEED")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(config.seed);
+
+    let mut rng = Rng::new(seed);
+
+    let found = {
+        // silence the panic output of this thread while inputs are probed.
+        let _silenced = silence_panics();
+        let check = |input: &str| run_check(&invariant, input);
+
+        (0..config.iterations).find_map(|iteration| {
+            let size = 1 + iteration * config.max_size.saturating_sub(1) / config.iterations.max(1);
+            let input = generate(&mut rng, size);
+            let failure = check(&input).err()?;
+            Some((iteration, shrink(&input, &failure, check)))
+        })
+    };
+
+    if let Some((iteration, (input, failure))) = found {
+        let path = write_example(day, &input);
+        let reason = match failure {
+            Failure::Panic(msg) => format!("panicked: {msg}"),
+            Failure::Violation(msg) => msg,
+        };
+        panic!(
+            "fuzzing failed on iteration {iteration} (seed {seed}): {reason}\nminimal input written to {path}:\n{input}"
+        );
+    }
+}
+
+thread_local! {
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores the silencing of panics when dropped, also if the generator panics.
+struct SilenceGuard {
+    previous: bool,
+}
+
+impl Drop for SilenceGuard {
+    fn drop(&mut self) {
+        SILENCED.set(self.previous);
+    }
+}
+
+/// Silences panic messages of the current thread until the guard is dropped.
+/// The global hook is wrapped once and still reports panics of all other threads,
+/// e.g. of tests that run in parallel.
+fn silence_panics() -> SilenceGuard {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENCED.get() {
+                previous(info);
+            }
+        }));
+    });
+
+    SilenceGuard {
+        previous: SILENCED.replace(true),
+    }
+}
+
+fn run_check(invariant: &impl Fn(&str) -> Result<(), String>, input: &str) -> Result<(), Failure> {
+    match panic::catch_unwind(AssertUnwindSafe(|| invariant(input))) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(msg)) => Err(Failure::Violation(msg)),
+        Err(payload) => {
+            let msg = payload
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".into());
+            Err(Failure::Panic(msg))
+        }
+    }
+}
+
+fn write_example(day: Day, input: &str) -> String {
+    let path = config::get()
+        .data_dir("examples")
+        .join(format!("{day}-fuzz.txt"));
+    if let Err(e) = fs::write(&path, input) {
+        eprintln!("Failed to write fuzzing example: {e}");
+    }
+    config::display_path(&path).to_string()
+}
+
+/// Shrinks a failing input while it keeps failing the same way.
+/// Lines are removed in shrinking chunks first, then numbers are made smaller.
+fn shrink(
+    input: &str,
+    failure: &Failure,
+    check: impl Fn(&str) -> Result<(), Failure>,
+) -> (String, Failure) {
+    let mut best = (input.to_string(), failure.clone());
+
+    let still_fails = |candidate: &str| match check(candidate) {
+        Err(f) if f.same_kind(failure) => Some(f),
+        _ => None,
+    };
+
+    loop {
+        let mut progress = false;
+
+        // remove chunks of lines, starting with large chunks.
+        let mut chunk = best.0.lines().count().div_ceil(2).max(1);
+        loop {
+            let lines: Vec<&str> = best.0.lines().collect();
+            let mut start = 0;
+            let mut removed = None;
+
+            while start < lines.len() {
+                let end = (start + chunk).min(lines.len());
+                let candidate = [&lines[..start], &lines[end..]].concat().join("\n");
+                if let Some(f) = still_fails(&candidate) {
+                    removed = Some((candidate, f));
+                    break;
+                }
+                start += chunk;
+            }
+
+            match removed {
+                Some(next) => {
+                    best = next;
+                    progress = true;
+                }
+                None if chunk == 1 => break,
+                None => chunk = chunk.div_ceil(2),
+            }
+        }
+
+        // make numbers smaller.
+        for (start, end) in number_spans(&best.0) {
+            let Ok(value) = best.0[start..end].parse::<u64>() else {
+                continue;
+            };
+
+            for smaller in [0, 1, value / 2, value.saturating_sub(1)] {
+                if smaller >= value {
+                    continue;
+                }
+                let candidate = format!("{}{smaller}{}", &best.0[..start], &best.0[end..]);
+                if let Some(f) = still_fails(&candidate) {
+                    best = (candidate, f);
+                    progress = true;
+                    break;
+                }
+            }
+
+            if progress {
+                // spans are invalidated by the replacement.
+                break;
+            }
+        }
+
+        if !progress {
+            return best;
+        }
+    }
+}
+
+fn number_spans(s: &str) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut start = None;
+
+    for (i, c) in s.char_indices() {
+        match (c.is_ascii_digit(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(st)) => {
+                spans.push((st, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(st) = start {
+        spans.push((st, s.len()));
+    }
+
+    spans
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{number_spans, shrink, Failure, Rng};
+
+    #[test]
+    fn rng_is_reproducible() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn rng_respects_bounds() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            let v = rng.between(3, 9);
+            assert!((3..=9).contains(&v));
+        }
+        assert_eq!(rng.between(5, 5), 5);
+    }
+
+    #[test]
+    fn finds_number_spans() {
+        assert_eq!(number_spans("a 12 b 3"), vec![(2, 4), (7, 8)]);
+        assert_eq!(number_spans("99"), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn shrinks_to_minimal_input() {
+        // fails whenever a line contains a number above 10.
+        let check = |input: &str| {
+            let too_big = input
+                .lines()
+                .any(|l| l.parse::<u64>().map_or(false, |n| n > 10));
+            if too_big {
+                Err(Failure::Violation("too big".into()))
+            } else {
+                Ok(())
+            }
+        };
+
+        let input = "1\n2\n500\n3\n4";
+        let failure = check(input).unwrap_err();
+        let (shrunk, _) = shrink(input, &failure, check);
+        assert_eq!(shrunk, "11");
+    }
+
+    #[test]
+    fn shrinking_keeps_failure_kind() {
+        // parsing panics on empty input, which must not count as the original violation.
+        let check = |input: &str| {
+            let n: u64 = input
+                .trim()
+                .parse()
+                .map_err(|_| Failure::Panic("parse".into()))?;
+            if n % 2 == 1 {
+                Err(Failure::Violation("odd".into()))
+            } else {
+                Ok(())
+            }
+        };
+
+        let (shrunk, failure) = shrink("77", &check("77").unwrap_err(), check);
+        assert_eq!(shrunk, "1");
+        assert_eq!(failure, Failure::Violation("odd".into()));
+    }
+}
@@ -0,0 +1,236 @@
+//! A dense two-dimensional grid, as found in many puzzle inputs.
+use std::fmt::Display;
+
+use crate::search::{self, Cost, Search};
+
+/// A position in a [`Grid`] as `(x, y)`, where `x` is the column and `y` the row.
+pub type Pos = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours, including diagonals.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from row-major cells. Panics if the number of cells does not match the size.
+    #[must_use]
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "cell count does not match size"
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid from lines of text, mapping every character with `f`.
+    /// Panics if the lines have different lengths.
+    pub fn parse(input: &str, f: impl Fn(char) -> T) -> Self {
+        let mut width = 0;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for line in input.lines().filter(|l| !l.is_empty()) {
+            let len = cells.len();
+            cells.extend(line.chars().map(&f));
+            let line_width = cells.len() - len;
+
+            if height == 0 {
+                width = line_width;
+            }
+            assert_eq!(width, line_width, "grid lines have different lengths");
+            height += 1;
+        }
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `pos` by `offset`, returns [`None`] if the result is outside of the grid.
+    #[must_use]
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Positions of the orthogonal neighbours of `pos` inside the grid.
+    pub fn neighbours_4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_4.iter().filter_map(move |d| self.step(pos, *d))
+    }
+
+    /// Positions of all neighbours of `pos` inside the grid, including diagonals.
+    pub fn neighbours_8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_8.iter().filter_map(move |d| self.step(pos, *d))
+    }
+
+    /// Iterates over all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Iterates over all positions and their cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Iterates over the cells of a row.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// Creates a grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl Fn(Pos, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+        }
+    }
+}
+
+/// Searches over the orthogonal steps of a grid, see [`search`].
+impl<T> Grid<T> {
+    /// Breadth-first search from all `starts`, taking steps for which `can_step(from, to)` holds, e.g. to avoid walls.
+    pub fn bfs(
+        &self,
+        starts: impl IntoIterator<Item = Pos>,
+        can_step: impl Fn(&T, &T) -> bool,
+    ) -> Search<Pos, usize> {
+        search::bfs(starts, |&pos| {
+            let cell = &self.cells[pos.1 * self.width + pos.0];
+            self.neighbours_4(pos)
+                .filter(|next| self.get(*next).is_some_and(|to| can_step(cell, to)))
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// Dijkstra's algorithm from all `starts`. `cost(from, to)` returns the cost of a step, or [`None`] if it is not possible.
+    pub fn dijkstra<C: Cost>(
+        &self,
+        starts: impl IntoIterator<Item = Pos>,
+        cost: impl Fn(&T, &T) -> Option<C>,
+    ) -> Search<Pos, C> {
+        search::dijkstra(starts, |&pos| {
+            let cell = &self.cells[pos.1 * self.width + pos.0];
+            self.neighbours_4(pos)
+                .filter_map(|next| Some((next, cost(cell, self.get(next)?)?)))
+                .collect::<Vec<_>>()
+        })
+    }
+}
+
+/// Manhattan distance between two positions, a heuristic for [`search::astar`] on grids.
+#[must_use]
+pub fn manhattan(a: Pos, b: Pos) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{manhattan, Grid};
+
+    #[test]
+    fn parses_and_indexes() {
+        let grid = Grid::parse("ab\ncd\nef\n", |c| c);
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get((1, 2)), Some(&'f'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "ab\ncd\nef\n");
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = Grid::parse("...\n...\n...", |c| c);
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours_8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn searches_paths() {
+        let grid = Grid::parse("S.#\n..#\n#..", |c| c);
+        let open = |_: &char, to: &char| *to != '#';
+
+        let search = grid.bfs([(0, 0)], open);
+        assert_eq!(search.distance(&(2, 2)), Some(4));
+        assert_eq!(search.distance(&(2, 0)), None);
+        assert_eq!(search.all_paths(&(1, 1)).len(), 2);
+
+        let search = grid.dijkstra([(0, 0)], |_, to| (*to != '#').then_some(2_u32));
+        assert_eq!(search.distance(&(2, 2)), Some(8));
+        assert_eq!(search.path(&(2, 2)).map(|p| p.len()), Some(5));
+        assert_eq!(manhattan((0, 0), (2, 2)), 4);
+    }
+}
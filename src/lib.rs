@@ -1,4 +1,12 @@
 mod day;
+pub mod fuzz;
+pub mod grid;
+pub mod math;
+pub mod memo;
+pub mod scan;
+pub mod search;
 pub mod template;
+pub mod trace;
+pub mod visualize;
 
 pub use day::*;
@@ -1,55 +1,144 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::solve::Input;
+use advent_of_code::template::commands::{
+    all, download, for_each_day, leaderboard, read, scaffold, solve, stress, tui, wait,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{ffi::OsString, path::PathBuf, process, time::Duration};
 
-    use advent_of_code::Day;
+    use pico_args::Arguments;
+
+    use advent_of_code::template::commands::all;
+    use advent_of_code::template::commands::solve::{self, Input};
+    use advent_of_code::template::config;
+    use advent_of_code::{Day, DaySelection};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: DaySelection,
         },
         Read {
-            day: Day,
+            days: DaySelection,
         },
         Scaffold {
-            day: Day,
+            days: DaySelection,
         },
         Solve {
+            days: DaySelection,
+            options: solve::Options,
+        },
+        Stress {
             day: Day,
+            scale: u32,
             release: bool,
-            time: bool,
-            submit: Option<u8>,
         },
-        All {
+        Tui {
             release: bool,
-            time: bool,
+        },
+        Wait {
+            day: Day,
+        },
+        Leaderboard {
+            id: String,
+            file: Option<PathBuf>,
+            year: Option<u16>,
+        },
+        All {
+            options: all::Options,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        // flags given on the command-line take precedence over the defaults of `aoc.toml`.
+        let defaults = &config::get().defaults;
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        if subcommand.as_deref() != Some("solve") && args.contains("--example") {
+            eprintln!("The `--example` option is only supported by `solve`.");
+            process::exit(1);
+        }
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                options: all::Options {
+                    release: parse_flag(&mut args, "--release", "--no-release", defaults.release),
+                    time: parse_flag(&mut args, "--time", "--no-time", defaults.time),
+                    report: args.opt_value_from_str("--report")?,
+                    save_baseline: args.opt_value_from_str("--save-baseline")?,
+                    baseline: args.opt_value_from_str("--baseline")?,
+                    threads: args.opt_value_from_str("--threads")?.or(defaults.threads),
+                    timeout: args
+                        .opt_value_from_str("--timeout")?
+                        .map(Duration::from_secs)
+                        .or(defaults.timeout),
+                    part: parse_part(&mut args)?,
+                    days: args.opt_free_from_str()?,
+                },
             },
-            Some("download") => AppArguments::Download {
+            Some("tui") => AppArguments::Tui {
+                release: parse_flag(&mut args, "--release", "--no-release", defaults.release),
+            },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                id: args.free_from_str()?,
+                file: args.opt_value_from_str("--file")?,
+                year: args.opt_value_from_str("--year")?,
+            },
+            Some("wait") => AppArguments::Wait {
                 day: args.free_from_str()?,
             },
+            Some("download") => AppArguments::Download {
+                days: args.free_from_str()?,
+            },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                days: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                days: args.free_from_str()?,
             },
-            Some("solve") => AppArguments::Solve {
+            Some("solve") => {
+                let mut options = solve::Options {
+                    release: parse_flag(&mut args, "--release", "--no-release", defaults.release),
+                    submit: args.opt_value_from_str("--submit")?,
+                    time: parse_flag(&mut args, "--time", "--no-time", defaults.time),
+                    compare: args.contains("--compare"),
+                    threads: args.opt_value_from_str("--threads")?.or(defaults.threads),
+                    speedup: args.contains("--speedup"),
+                    trace: args.contains("--trace"),
+                    visualize: args.contains("--visualize"),
+                    part: parse_part(&mut args)?,
+                    input: args
+                        .opt_value_from_str::<_, PathBuf>("--input")?
+                        .map(|path| {
+                            if path.as_os_str() == "-" {
+                                Input::Stdin
+                            } else {
+                                Input::File(path)
+                            }
+                        }),
+                };
+
+                // `--example` takes an optional value, which pico-args can't parse.
+                let mut rest = std::mem::replace(&mut args, Arguments::from_vec(vec![])).finish();
+                if let Some(example) = take_example(&mut rest) {
+                    if options.input.is_some() {
+                        eprintln!("The `--input` and `--example` options can't be combined.");
+                        process::exit(1);
+                    }
+                    options.input = Some(Input::Example(example));
+                }
+                args = Arguments::from_vec(rest);
+
+                AppArguments::Solve {
+                    days: args.free_from_str()?,
+                    options,
+                }
+            }
+            Some("stress") => AppArguments::Stress {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                scale: args.opt_value_from_str("--scale")?.unwrap_or(8),
+                release: parse_flag(&mut args, "--release", "--no-release", defaults.release),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -68,6 +157,86 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_part(args: &mut pico_args::Arguments) -> Result<Option<u8>, pico_args::Error> {
+        let part = args.opt_value_from_str("--part")?;
+        if part.is_some_and(|p| p != 1 && p != 2) {
+            eprintln!("Unexpected command-line input. Format: --part 1|2");
+            process::exit(1);
+        }
+        Ok(part)
+    }
+
+    /// Parses a flag that can also be set in aoc.toml, `--no-<flag>` turns it off for a single run.
+    fn parse_flag(
+        args: &mut pico_args::Arguments,
+        flag: &'static str,
+        negation: &'static str,
+        default: bool,
+    ) -> bool {
+        let enabled = args.contains(flag);
+        let disabled = args.contains(negation);
+        !disabled && (enabled || default)
+    }
+
+    /// Removes `--example [name]` from the arguments that are left after the options of `solve`.
+    /// A free argument after the flag is only taken as the name if another one is left for the day,
+    /// so `solve 1 --example 2` runs example `2` of day 1 and `solve --example 1` the example of day 1.
+    fn take_example(args: &mut Vec<OsString>) -> Option<Option<String>> {
+        let index = args.iter().position(|x| x == "--example")?;
+        args.remove(index);
+
+        let is_free = |x: &OsString| x.to_str().is_some_and(|x| !x.starts_with('-'));
+        let has_name =
+            args.get(index).is_some_and(is_free) && args.iter().filter(|x| is_free(x)).count() > 1;
+
+        Some(has_name.then(|| args.remove(index).to_string_lossy().into_owned()))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{parse_flag, take_example};
+        use std::ffi::OsString;
+
+        fn take(args: &[&str]) -> (Option<Option<String>>, Vec<OsString>) {
+            let mut args = args.iter().map(OsString::from).collect();
+            (take_example(&mut args), args)
+        }
+
+        #[test]
+        fn takes_example_after_day() {
+            assert_eq!(
+                take(&["01", "--example", "2"]),
+                (Some(Some("2".into())), vec!["01".into()])
+            );
+            assert_eq!(
+                take(&["--example", "2", "01"]),
+                (Some(Some("2".into())), vec!["01".into()])
+            );
+            assert_eq!(take(&["--example", "01"]), (Some(None), vec!["01".into()]));
+            assert_eq!(take(&["01", "--example"]), (Some(None), vec!["01".into()]));
+            assert_eq!(take(&["01"]), (None, vec!["01".into()]));
+        }
+
+        #[test]
+        fn negated_flags_win_over_defaults() {
+            let flag = |args: &[&str], default| {
+                let args = args.iter().map(OsString::from).collect();
+                parse_flag(
+                    &mut pico_args::Arguments::from_vec(args),
+                    "--time",
+                    "--no-time",
+                    default,
+                )
+            };
+
+            assert!(flag(&[], true));
+            assert!(flag(&["--time"], false));
+            assert!(!flag(&[], false));
+            assert!(!flag(&["--no-time"], true));
+            assert!(!flag(&["--time", "--no-time"], true));
+        }
+    }
 }
 
 fn main() {
@@ -77,16 +246,33 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
+            AppArguments::All { options } => all::handle(&options),
+            AppArguments::Tui { release } => tui::handle(release),
+            AppArguments::Leaderboard { id, file, year } => {
+                leaderboard::handle(&id, file.as_deref(), year);
+            }
+            AppArguments::Wait { day } => wait::handle(day),
+            AppArguments::Download { days } => download::handle(&days),
+            AppArguments::Read { days } => read::handle(&days),
+            AppArguments::Scaffold { days } => scaffold::handle_batch(&days),
+            AppArguments::Solve { days, options } => {
+                let is_batch = days.single().is_none();
+                let has_own_input = matches!(options.input, Some(Input::File(_) | Input::Stdin));
+
+                if is_batch && (options.submit.is_some() || has_own_input) {
+                    eprintln!(
+                        "The `--submit` and `--input` options can only be used with a single day."
+                    );
+                    std::process::exit(1);
+                }
+
+                for_each_day(&days, |day| solve::handle(day, &options));
+            }
+            AppArguments::Stress {
                 day,
+                scale,
                 release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+            } => stress::handle(day, scale, release),
         },
     };
 }
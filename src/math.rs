@@ -0,0 +1,325 @@
+//! Integer and number theory helpers: gcd and lcm, modular arithmetic, the chinese remainder theorem,
+//! exact quadratic solving and overflow-checked `i128` arithmetic.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+/// Greatest common divisor, `gcd(0, 0)` is `0`.
+#[must_use]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or [`None`] if it overflows. `lcm(0, n)` is `0`.
+#[must_use]
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Greatest common divisor of all numbers, `0` if there are none.
+pub fn gcd_of(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/// Least common multiple of all numbers, `1` if there are none. [`None`] if it overflows.
+pub fn lcm_of(numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(1, lcm)
+}
+
+/// Extended euclidean algorithm: returns `(g, x, y)` with `g = gcd(a, b) >= 0` and `a·x + b·y = g`.
+#[must_use]
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (i128::from(a), i128::from(b));
+    let (mut old_x, mut x) = (1_i128, 0_i128);
+    let (mut old_y, mut y) = (0_i128, 1_i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+
+    // the coefficients are bounded by |a| and |b|, only gcd(i64::MIN, 0) does not fit.
+    let narrow = |n: i128| i64::try_from(n).expect("gcd does not fit into i64");
+    (narrow(old_r), narrow(old_x), narrow(old_y))
+}
+
+/// Inverse of `a` modulo `m` in `0..m`, if `a` and `m` are coprime. `m` must be positive.
+#[must_use]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base^exp` modulo `m` in `0..m`, by repeated squaring. `m` must be positive.
+#[must_use]
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    assert!(m > 0, "modulus must be positive");
+    let m = i128::from(m);
+    let mut base = i128::from(base).rem_euclid(m);
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    i64::try_from(result).expect("reduced modulo an i64")
+}
+
+/// Solves a system of congruences `x ≡ r (mod m)` given as `(r, m)` pairs, with positive moduli.
+///
+/// Returns `(x, lcm)` with `x` in `0..lcm`, so every solution is `x + k·lcm`. Moduli don't have to
+/// be coprime. Returns [`None`] if the congruences contradict each other or the lcm overflows `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let mut x = 0_i128;
+    let mut modulus = 1_i128;
+
+    for (r, m) in congruences {
+        assert!(m > 0, "modulus must be positive");
+        let (r, m) = (i128::from(r).rem_euclid(i128::from(m)), i128::from(m));
+
+        // x + k·modulus ≡ r (mod m)  ⇔  k·modulus ≡ r - x (mod m)
+        let (g, inverse, _) =
+            extended_gcd(i64::try_from(modulus % m).ok()?, i64::try_from(m).ok()?);
+        let g = i128::from(g);
+        if (r - x) % g != 0 {
+            return None;
+        }
+
+        let step = m / g;
+        let k = ((r - x) / g % step * i128::from(inverse)).rem_euclid(step);
+        x += k * modulus;
+        modulus = i128::from(i64::try_from(modulus * step).ok()?);
+        x = x.rem_euclid(modulus);
+    }
+
+    Some((i64::try_from(x).ok()?, i64::try_from(modulus).ok()?))
+}
+
+/// Integer square root, rounded down.
+#[must_use]
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // newton's method, starting above the root so it decreases monotonically.
+    let mut x = 1_u128 << (n.ilog2() / 2 + 1);
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// Integers `x` for which `a·x² + b·x + c < 0`, i.e. strictly between the two roots.
+/// `a` must be positive. Computed without floating point, so integer roots are excluded exactly.
+///
+/// Panics if intermediate values overflow `i128`.
+#[must_use]
+pub fn quadratic_below(a: i64, b: i64, c: i64) -> Option<RangeInclusive<i64>> {
+    assert!(a > 0, "leading coefficient must be positive");
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+
+    let discriminant = expect(discriminant(a, b, c));
+    if discriminant <= 0 {
+        return None;
+    }
+
+    let f = |x: i128| expect(evaluate(a, b, c, x));
+    let root = i128::try_from(isqrt(discriminant.unsigned_abs())).expect("fits");
+
+    // rounding the roots of the integer square root is off by at most one, correct by evaluating.
+    // the bounds may meet without an integer in between, e.g. for roots 0.2 and 0.8.
+    let mut low = (-b - root).div_euclid(2 * a);
+    let mut high = (-b + root).div_euclid(2 * a) + 1;
+    while low <= high && f(low) >= 0 {
+        low += 1;
+    }
+    while high >= low && f(high) >= 0 {
+        high -= 1;
+    }
+    if low > high {
+        return None;
+    }
+
+    while f(low - 1) < 0 {
+        low -= 1;
+    }
+    while f(high + 1) < 0 {
+        high += 1;
+    }
+
+    Some(expect(narrow(low))..=expect(narrow(high)))
+}
+
+/// Integer roots of `a·x² + b·x + c = 0` in ascending order, `a` must not be zero.
+#[must_use]
+pub fn integer_roots(a: i64, b: i64, c: i64) -> Vec<i64> {
+    assert!(a != 0, "leading coefficient must not be zero");
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+
+    let discriminant = expect(discriminant(a, b, c));
+    if discriminant < 0 {
+        return vec![];
+    }
+
+    let root = i128::try_from(isqrt(discriminant.unsigned_abs())).expect("fits");
+    if root * root != discriminant {
+        return vec![];
+    }
+
+    let mut roots: Vec<i64> = [-b - root, -b + root]
+        .into_iter()
+        .filter(|n| n % (2 * a) == 0)
+        .filter_map(|n| i64::try_from(n / (2 * a)).ok())
+        .collect();
+
+    roots.sort_unstable();
+    roots.dedup();
+    roots
+}
+
+/// An arithmetic operation overflowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError;
+
+impl Display for OverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "integer overflow")
+    }
+}
+
+impl Error for OverflowError {}
+
+/// Overflow-checked `a + b`, for use with `?` in solutions that return a [`Result`].
+pub fn add(a: i128, b: i128) -> Result<i128, OverflowError> {
+    a.checked_add(b).ok_or(OverflowError)
+}
+
+/// Overflow-checked `a - b`.
+pub fn sub(a: i128, b: i128) -> Result<i128, OverflowError> {
+    a.checked_sub(b).ok_or(OverflowError)
+}
+
+/// Overflow-checked `a · b`.
+pub fn mul(a: i128, b: i128) -> Result<i128, OverflowError> {
+    a.checked_mul(b).ok_or(OverflowError)
+}
+
+/// Overflow-checked `base^exp`.
+pub fn pow(base: i128, exp: u32) -> Result<i128, OverflowError> {
+    base.checked_pow(exp).ok_or(OverflowError)
+}
+
+/// Converts a value to a narrower integer type, e.g. the `u64` answer of a part.
+pub fn narrow<T: TryFrom<i128>>(n: i128) -> Result<T, OverflowError> {
+    T::try_from(n).map_err(|_| OverflowError)
+}
+
+fn discriminant(a: i128, b: i128, c: i128) -> Result<i128, OverflowError> {
+    sub(mul(b, b)?, mul(mul(4, a)?, c)?)
+}
+
+fn evaluate(a: i128, b: i128, c: i128, x: i128) -> Result<i128, OverflowError> {
+    add(mul(add(mul(a, x)?, b)?, x)?, c)
+}
+
+fn expect<T>(result: Result<T, OverflowError>) -> T {
+    result.expect("quadratic coefficients overflow i128")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        add, crt, extended_gcd, gcd, gcd_of, integer_roots, isqrt, lcm, lcm_of, mod_inverse,
+        mod_pow, mul, narrow, pow, quadratic_below, OverflowError,
+    };
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(gcd_of([12, 18, 27]), 3);
+        assert_eq!(lcm_of([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_of([]), Some(1));
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(i64::MAX - 1, u64::MAX, i64::MAX), i64::MAX - 1);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli don't have to be coprime.
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn solves_quadratics_exactly() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+
+        // (x - 2)(x - 8) < 0 excludes the integer roots.
+        assert_eq!(quadratic_below(1, -10, 16), Some(3..=7));
+        assert_eq!(quadratic_below(1, -7, 9), Some(2..=5));
+        assert_eq!(quadratic_below(1, -4, 4), None);
+        assert_eq!(quadratic_below(1, 0, 1), None);
+        assert_eq!(quadratic_below(1, -1, 0), None);
+        assert_eq!(quadratic_below(5, -5, 1), None);
+        assert_eq!(quadratic_below(2, 0, -8), Some(-1..=1));
+
+        assert_eq!(integer_roots(1, -10, 16), vec![2, 8]);
+        assert_eq!(integer_roots(1, -4, 4), vec![2]);
+        assert_eq!(integer_roots(2, -3, 1), vec![1]);
+        assert_eq!(integer_roots(1, 0, -2), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn checks_overflow() {
+        assert_eq!(add(1, 2), Ok(3));
+        assert_eq!(mul(i128::MAX, 2), Err(OverflowError));
+        assert_eq!(pow(10, 38), Ok(10_i128.pow(38)));
+        assert_eq!(pow(10, 39), Err(OverflowError));
+        assert_eq!(narrow::<u8>(256), Err(OverflowError));
+        assert_eq!(narrow::<u64>(42), Ok(42));
+    }
+}
@@ -0,0 +1,118 @@
+//! Memoization for recursive solutions, e.g. counting arrangements or dynamic programming over strings.
+//!
+//! The cache is an explicit [`Memo`] value that is passed through the recursion. Create it inside
+//! the part (or [`clear`](Memo::clear) it before reuse), so every bench iteration starts cold and
+//! `--time` measures the actual work instead of cache lookups.
+//!
+//! ```
+//! fn count(memo: &mut Memo<(usize, usize), u64>, springs: &[u8], groups: &[usize]) -> u64 {
+//!     memo.get_or_compute((springs.len(), groups.len()), |memo| {
+//!         // ...recurses with `count(memo, &springs[1..], groups)`
+//!     })
+//! }
+//!
+//! let total: u64 = rows.iter().map(|(s, g)| count(&mut Memo::new(), s, g)).sum();
+//! ```
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cache of the results of a recursive function, by its arguments.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached result for `key`, or computes and caches it with `f`.
+    /// `f` gets the memo back to make recursive calls.
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+
+        let value = f(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the cached result for `key`, if any.
+    #[must_use]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Caches a result directly, e.g. to seed base cases.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    /// Number of cached results.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Removes all cached results, keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Memo;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_compute(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+    }
+
+    /// Number of ways to build `word` from `pieces`, keyed by the length of the remaining suffix.
+    fn arrangements(memo: &mut Memo<usize, u64>, word: &str, pieces: &[&str]) -> u64 {
+        if word.is_empty() {
+            return 1;
+        }
+        memo.get_or_compute(word.len(), |memo| {
+            pieces
+                .iter()
+                .filter_map(|piece| word.strip_prefix(piece))
+                .map(|rest| arrangements(memo, rest, pieces))
+                .sum()
+        })
+    }
+
+    #[test]
+    fn caches_recursive_calls() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(memo.get(&10), Some(&55));
+
+        memo.clear();
+        assert!(memo.is_empty());
+
+        let pieces = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"];
+        assert_eq!(arrangements(&mut Memo::new(), "gbbr", &pieces), 4);
+        assert_eq!(arrangements(&mut Memo::new(), "ubwu", &pieces), 0);
+    }
+}
@@ -0,0 +1,330 @@
+//! Pattern-based parsing of input lines with typed captures.
+//!
+//! Patterns are literal text with `{}` placeholders. Whitespace in a pattern matches one or more
+//! whitespace characters, so aligned columns like `Card  1:` need no special care. Every placeholder
+//! captures text up to the next literal part, is trimmed and parsed into its type with [`FromStr`].
+//! A type in brackets captures a list: `[T]` splits on whitespace and `[T; ","]` on a separator.
+//!
+//! ```
+//! use advent_of_code::scan;
+//!
+//! let (card, winning, numbers) = scan!(line, "Card {}: {} | {}" => u32, [u32], [u32])?;
+//! let (game, sets) = scan!(line, "Game {}: {}" => u8, [Set; ";"])?;
+//! ```
+//!
+//! On mismatch, the returned [`Error`] names the line, and the capture and type that failed to parse.
+//! A pattern with more or fewer placeholders than types is rejected at compile time.
+use std::error::Error as StdError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Error {
+    /// The line that was scanned.
+    pub line: String,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// A literal part of the pattern was not found.
+    Mismatch { expected: String },
+    /// The text of a capture could not be parsed.
+    Parse {
+        text: String,
+        type_name: &'static str,
+        source: Box<dyn StdError + Send + Sync>,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to scan \"{}\": ", self.line)?;
+        match &self.kind {
+            ErrorKind::Mismatch { expected } => write!(f, "expected \"{expected}\""),
+            ErrorKind::Parse {
+                text, type_name, ..
+            } => write!(f, "invalid {type_name} \"{text}\""),
+        }
+    }
+}
+
+/// Errors are equal if they fail at the same place, sources are compared by their message.
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.line == other.line
+            && match (&self.kind, &other.kind) {
+                (
+                    ErrorKind::Mismatch { expected },
+                    ErrorKind::Mismatch {
+                        expected: other_expected,
+                    },
+                ) => expected == other_expected,
+                (
+                    ErrorKind::Parse {
+                        text,
+                        type_name,
+                        source,
+                    },
+                    ErrorKind::Parse {
+                        text: other_text,
+                        type_name: other_type_name,
+                        source: other_source,
+                    },
+                ) => {
+                    text == other_text
+                        && type_name == other_type_name
+                        && source.to_string() == other_source.to_string()
+                }
+                _ => false,
+            }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match &self.kind {
+            ErrorKind::Parse { source, .. } => Some(source.as_ref()),
+            ErrorKind::Mismatch { .. } => None,
+        }
+    }
+}
+
+/// Matches `line` against `pattern` and returns the trimmed text of every placeholder.
+/// Used by [`scan!`](crate::scan!), which parses the captures into their types.
+pub fn captures<'a>(line: &'a str, pattern: &str) -> Result<Vec<&'a str>, Error> {
+    let mut literals = pattern.split("{}");
+    let mut rest = line;
+    let mut captures = vec![];
+
+    let mismatch = |expected: &str| Error {
+        line: line.to_string(),
+        kind: ErrorKind::Mismatch {
+            expected: expected.to_string(),
+        },
+    };
+
+    let prefix = literals.next().unwrap_or_default();
+    rest = match_literal(rest, prefix).ok_or_else(|| mismatch(prefix))?;
+
+    let mut literals = literals.peekable();
+    while let Some(literal) = literals.next() {
+        if literals.peek().is_none() && literal.is_empty() {
+            // the last placeholder captures the rest of the line.
+            captures.push(rest.trim());
+            rest = "";
+            break;
+        }
+
+        // captures are lazy, they end at the first match of the following literal.
+        let (capture, after) = rest
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([rest.len()])
+            .find_map(|i| Some((&rest[..i], match_literal(&rest[i..], literal)?)))
+            .ok_or_else(|| mismatch(literal))?;
+
+        captures.push(capture.trim());
+        rest = after;
+    }
+
+    if !rest.trim().is_empty() {
+        return Err(mismatch("end of line"));
+    }
+
+    Ok(captures)
+}
+
+/// Matches a literal at the start of `text`, returning the text after it.
+fn match_literal<'a>(mut text: &'a str, literal: &str) -> Option<&'a str> {
+    let mut chars = literal.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                chars.next();
+            }
+            let trimmed = text.trim_start();
+            if trimmed.len() == text.len() {
+                return None;
+            }
+            text = trimmed;
+        } else {
+            text = text.strip_prefix(c)?;
+        }
+    }
+
+    Some(text)
+}
+
+/// Parses a single capture.
+pub fn parse<T>(line: &str, text: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: StdError + Send + Sync + 'static,
+{
+    text.parse().map_err(|e| Error {
+        line: line.to_string(),
+        kind: ErrorKind::Parse {
+            text: text.to_string(),
+            type_name: short_type_name::<T>(),
+            source: Box::new(e),
+        },
+    })
+}
+
+/// Parses a capture as a list, split on whitespace or on `separator`.
+pub fn parse_list<T>(line: &str, text: &str, separator: Option<&str>) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: StdError + Send + Sync + 'static,
+{
+    match separator {
+        Some(separator) if !text.is_empty() => text
+            .split(separator)
+            .map(|item| parse(line, item.trim()))
+            .collect(),
+        Some(_) => Ok(vec![]),
+        None => text
+            .split_whitespace()
+            .map(|item| parse(line, item))
+            .collect(),
+    }
+}
+
+/// Counts the `{}` placeholders of a pattern, so [`scan!`](crate::scan!) can check them
+/// against its types at compile time.
+#[must_use]
+pub const fn placeholders(pattern: &str) -> usize {
+    let bytes = pattern.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        if bytes[i] == b'{' && bytes[i + 1] == b'}' {
+            count += 1;
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+    count
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Matches a line against a pattern with `{}` placeholders and parses the captures into a tuple.
+/// See the [`scan`](crate::scan) module for the pattern syntax.
+#[macro_export]
+macro_rules! scan {
+    (@capture $line:ident, $captures:ident, [$ty:ty; $separator:literal]) => {
+        $crate::scan::parse_list::<$ty>($line, $captures.next().unwrap_or_default(), Some($separator))
+    };
+    (@capture $line:ident, $captures:ident, [$ty:ty]) => {
+        $crate::scan::parse_list::<$ty>($line, $captures.next().unwrap_or_default(), None)
+    };
+    (@capture $line:ident, $captures:ident, $ty:ty) => {
+        $crate::scan::parse::<$ty>($line, $captures.next().unwrap_or_default())
+    };
+    // splits the types one at a time, as a type like `HashMap<u8, u8>` is not a single token tree.
+    (@types $pattern:literal, $line:ident, $captures:ident, [$($parsed:tt)*]; [$ty:ty; $separator:literal] $(, $($rest:tt)*)?) => {
+        $crate::scan!(@types $pattern, $line, $captures, [$($parsed)* ([$ty; $separator])]; $($($rest)*)?)
+    };
+    (@types $pattern:literal, $line:ident, $captures:ident, [$($parsed:tt)*]; [$ty:ty] $(, $($rest:tt)*)?) => {
+        $crate::scan!(@types $pattern, $line, $captures, [$($parsed)* ([$ty])]; $($($rest)*)?)
+    };
+    (@types $pattern:literal, $line:ident, $captures:ident, [$($parsed:tt)*]; $ty:ty $(, $($rest:tt)*)?) => {
+        $crate::scan!(@types $pattern, $line, $captures, [$($parsed)* ($ty)]; $($($rest)*)?)
+    };
+    (@types $pattern:literal, $line:ident, $captures:ident, [$(($($ty:tt)*))+];) => {{
+        const _ASSERT: () = assert!(
+            $crate::scan::placeholders($pattern) == [$(stringify!($($ty)*)),+].len(),
+            "pattern placeholders and types of `scan!` don't match"
+        );
+        Ok(($($crate::scan!(@capture $line, $captures, $($ty)*)?,)+))
+    }};
+    ($line:expr, $pattern:literal => $($types:tt)+) => {{
+        let line: &str = $line;
+        (|| -> Result<_, $crate::scan::Error> {
+            let captures = $crate::scan::captures(line, $pattern)?;
+            let mut captures = captures.into_iter();
+            $crate::scan!(@types $pattern, line, captures, []; $($types)+)
+        })()
+    }};
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{captures, placeholders};
+    use crate::template::runner::render_error;
+
+    #[test]
+    fn captures_placeholders() {
+        assert_eq!(
+            captures("Card  1: 41 48 | 83  86", "Card {}: {} | {}").unwrap(),
+            vec!["1", "41 48", "83  86"]
+        );
+        assert_eq!(captures("a=1,b=", "a={},b={}").unwrap(), vec!["1", ""]);
+        assert!(captures("Card 1 41 48", "Card {}: {}").is_err());
+        assert!(captures("x 1 y", "x {}").is_ok());
+        assert!(captures("x 1 y", "x {} z").is_err());
+        assert!(captures("1 2 extra", "{} {}").is_ok());
+        assert!(captures("[1] extra", "[{}]").is_err());
+    }
+
+    #[test]
+    fn counts_placeholders() {
+        assert_eq!(placeholders("Card {}: {} | {}"), 3);
+        assert_eq!(placeholders("{}{}"), 2);
+        assert_eq!(placeholders("{ } {"), 0);
+        assert_eq!(placeholders(""), 0);
+    }
+
+    #[test]
+    fn parses_typed_captures() {
+        let line = "Game 3: 8 green, 6 blue";
+        let (game, cubes) = scan!(line, "Game {}: {}" => u8, [String; ","]).unwrap();
+        assert_eq!(game, 3);
+        assert_eq!(cubes, vec!["8 green", "6 blue"]);
+
+        let (numbers,) = scan!("Time:      7  15   30", "Time: {}" => [u64]).unwrap();
+        assert_eq!(numbers, vec![7, 15, 30]);
+
+        let error = scan!("Card x: 1 | 2", "Card {}: {} | {}" => u32, [u32], [u32]).unwrap_err();
+        assert_eq!(
+            render_error(&error),
+            "failed to scan \"Card x: 1 | 2\": invalid u32 \"x\"\n  caused by: invalid digit found in string"
+        );
+
+        let error = scan!("Card 1 1 2", "Card {}: {}" => u32, [u32]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to scan \"Card 1 1 2\": expected \": \""
+        );
+    }
+
+    #[test]
+    fn parses_path_types() {
+        use std::net::Ipv4Addr;
+
+        let line = "route 10.0.0.1 via 10.0.0.2, 10.0.0.3";
+        let (from, via) =
+            scan!(line, "route {} via {}" => std::net::Ipv4Addr, [Ipv4Addr; ","]).unwrap();
+        assert_eq!(from, Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(
+            via,
+            vec![Ipv4Addr::new(10, 0, 0, 2), Ipv4Addr::new(10, 0, 0, 3)]
+        );
+
+        let (ids,) = scan!("ids: 3 1 2", "ids: {}" => [u8],).unwrap();
+        assert_eq!(ids, vec![3, 1, 2]);
+
+        let error = scan!("to 10.0.0", "to {}" => std::net::Ipv4Addr).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to scan \"to 10.0.0\": invalid Ipv4Addr \"10.0.0\""
+        );
+    }
+}
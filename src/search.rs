@@ -0,0 +1,340 @@
+//! Shortest path searches over implicit graphs: breadth-first search, Dijkstra and A*.
+//!
+//! States can be any hashable type, e.g. a grid position or a `(position, direction)` pair.
+//! Neighbours are produced by a closure, with a cost per step for weighted searches.
+//! All searches accept several start states and record every shortest predecessor of a state,
+//! so paths can be reconstructed afterwards:
+//!
+//! ```
+//! // states are positions and the directions they were entered from.
+//! let search = search::dijkstra_until(
+//!     [(start, None)],
+//!     |&(pos, from)| next_steps(&grid, pos, from),
+//!     |&(pos, _)| pos == goal,
+//! );
+//! let cost = search.goal().and_then(|goal| search.distance(goal));
+//! let path = search.goal().and_then(|goal| search.path(goal));
+//! ```
+//!
+//! Searches over plain grid positions don't need a closure, see [`Grid::bfs`](crate::grid::Grid::bfs)
+//! and [`Grid::dijkstra`](crate::grid::Grid::dijkstra).
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Cost of a step in a weighted search, e.g. `u32` or `u64`. [`Default`] has to be zero.
+/// Paths can only be reconstructed if there are no cycles of steps without cost.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// The result of a search: distances of all reached states and their shortest predecessors.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    distances: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+    goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// Distance from the closest start to `state`, if it was reached.
+    #[must_use]
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    /// Distances of all reached states. Searches that stop at a goal may include states
+    /// whose distance was not final yet.
+    #[must_use]
+    pub fn distances(&self) -> &HashMap<S, C> {
+        &self.distances
+    }
+
+    /// All states that precede `state` on one of its shortest paths. Empty for starts.
+    #[must_use]
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// The goal the search stopped at, for searches with a goal.
+    #[must_use]
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// A shortest path from a start to `state`, including both.
+    #[must_use]
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.distances.get(state)?;
+
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors(path.last()?).first() {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// All shortest paths from the starts to `state`. Their number can grow exponentially.
+    #[must_use]
+    pub fn all_paths(&self, state: &S) -> Vec<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return vec![];
+        }
+
+        let predecessors = self.predecessors(state);
+        if predecessors.is_empty() {
+            return vec![vec![state.clone()]];
+        }
+
+        predecessors
+            .iter()
+            .flat_map(|previous| self.all_paths(previous))
+            .map(|mut path| {
+                path.push(state.clone());
+                path
+            })
+            .collect()
+    }
+
+    /// Records `state` as reached with `distance` via `previous`.
+    /// Returns `true` if this improved the distance of `state`.
+    fn relax(&mut self, state: &S, distance: C, previous: &S) -> bool
+    where
+        C: Ord,
+    {
+        match self.distances.get(state) {
+            Some(known) if distance > *known => false,
+            Some(known) if distance == *known => {
+                let predecessors = self.predecessors.entry(state.clone()).or_default();
+                if !predecessors.contains(previous) {
+                    predecessors.push(previous.clone());
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(state.clone(), distance);
+                self.predecessors
+                    .insert(state.clone(), vec![previous.clone()]);
+                true
+            }
+        }
+    }
+}
+
+/// Breadth-first search from all `starts`, exploring every reachable state.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    bfs_until(starts, neighbours, |_| false)
+}
+
+/// Breadth-first search from all `starts` that stops at the first state for which `is_goal` holds.
+pub fn bfs_until<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if search.distances.insert(start.clone(), 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        // all states of the previous level were expanded, so the goal has all its predecessors.
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        let distance = search.distances[&state] + 1;
+        for next in neighbours(&state) {
+            if search.relax(&next, distance, &state) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm from all `starts`, exploring every reachable state.
+/// `neighbours` returns the next states with the costs of the steps to them.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), |_| false)
+}
+
+/// Dijkstra's algorithm from all `starts` that stops at the first state for which `is_goal` holds.
+pub fn dijkstra_until<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search from all `starts` to the first state for which `is_goal` holds.
+///
+/// `heuristic` estimates the remaining cost to a goal. It must never overestimate it and
+/// be consistent, e.g. the manhattan distance on a grid with a minimum step cost of one.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new();
+    // states are kept out of the heap, so they don't have to be `Ord`.
+    let mut states = vec![];
+    let mut heap = BinaryHeap::new();
+    let mut goal_priority = None;
+
+    for start in starts {
+        if search
+            .distances
+            .insert(start.clone(), C::default())
+            .is_none()
+        {
+            heap.push(Reverse((heuristic(&start), C::default(), states.len())));
+            states.push(start);
+        }
+    }
+
+    while let Some(Reverse((priority, distance, index))) = heap.pop() {
+        // keep expanding ties of the goal, they may be predecessors on other shortest paths.
+        if goal_priority.is_some_and(|goal| priority > goal) {
+            break;
+        }
+
+        let state = states[index].clone();
+        if search.distances.get(&state).is_some_and(|d| distance > *d) {
+            continue;
+        }
+
+        if goal_priority.is_none() && is_goal(&state) {
+            goal_priority = Some(priority);
+            search.goal = Some(state);
+            continue;
+        }
+
+        for (next, cost) in neighbours(&state) {
+            let next_distance = distance + cost;
+            if search.relax(&next, next_distance, &state) {
+                heap.push(Reverse((
+                    next_distance + heuristic(&next),
+                    next_distance,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+
+    search
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_until, dijkstra, dijkstra_until};
+
+    /// 0 → 1 (1), 0 → 2 (4), 1 → 2 (2), 1 → 3 (6), 2 → 3 (3), 4 is unreachable.
+    fn edges(node: &u8) -> Vec<(u8, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 4)],
+            1 => vec![(2, 2), (3, 6)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn finds_weighted_shortest_paths() {
+        let search = dijkstra([0], edges);
+        assert_eq!(search.distance(&2), Some(3));
+        assert_eq!(search.distance(&3), Some(6));
+        assert_eq!(search.distance(&4), None);
+        assert_eq!(search.path(&3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(search.path(&0), Some(vec![0]));
+
+        let search = dijkstra_until([0], edges, |n| *n == 2);
+        assert_eq!(search.goal(), Some(&2));
+        assert_eq!(search.distance(&2), Some(3));
+
+        let search = astar([0], edges, |n| u32::from(3 - n.min(&3)), |n| *n == 3);
+        assert_eq!(search.distance(&3), Some(6));
+    }
+
+    #[test]
+    fn finds_all_shortest_paths() {
+        // a diamond: 0 → 1 → 3 and 0 → 2 → 3.
+        let diamond = |n: &u8| match n {
+            0 => vec![1, 2],
+            1 | 2 => vec![3],
+            _ => vec![],
+        };
+
+        let search = bfs([0], diamond);
+        assert_eq!(search.distance(&3), Some(2));
+        assert_eq!(search.predecessors(&3), &[1, 2]);
+        assert_eq!(search.all_paths(&3), vec![vec![0, 1, 3], vec![0, 2, 3]]);
+
+        let weighted = |n: &u8| diamond(n).into_iter().map(|next| (next, 1_u32));
+        let search = astar([0], weighted, |_| 0, |n| *n == 3);
+        assert_eq!(search.all_paths(&3).len(), 2);
+    }
+
+    #[test]
+    fn searches_from_several_starts() {
+        let line = |n: &i32| [n - 1, n + 1].into_iter().filter(|n| (0..10).contains(n));
+
+        let search = bfs([0, 9], line);
+        assert_eq!(search.distance(&4), Some(4));
+        assert_eq!(search.distance(&6), Some(3));
+        assert_eq!(search.path(&7), Some(vec![9, 8, 7]));
+
+        let search = bfs_until([0, 9], line, |n| *n == 2);
+        assert_eq!(search.goal(), Some(&2));
+        assert_eq!(search.distance(&5), None);
+    }
+}
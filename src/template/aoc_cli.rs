@@ -1,9 +1,12 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    path::Path,
     process::{Command, Output, Stdio},
 };
 
+use crate::template::config::{self, display_path};
+use crate::template::crypt;
 use crate::Day;
 
 #[derive(Debug)]
@@ -69,8 +72,24 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
 
     let output = call_aoc_cli(&args)?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        display_path(Path::new(&input_path))
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        display_path(Path::new(&puzzle_path))
+    );
+
+    if crypt::is_enabled() {
+        for path in [&input_path, &puzzle_path] {
+            match crypt::encrypt_file(Path::new(path)) {
+                Ok(encrypted) => println!("🔒 Encrypted to \"{}\".", display_path(&encrypted)),
+                Err(e) => eprintln!("Failed to encrypt \"{path}\": {e}"),
+            }
+        }
+    }
+
     Ok(output)
 }
 
@@ -83,18 +102,19 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    let path = config::get().data_dir("inputs").join(format!("{day}.txt"));
+    path.to_string_lossy().into_owned()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    let path = config::get().data_dir("puzzles").join(format!("{day}.md"));
+    path.to_string_lossy().into_owned()
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+/// The year configured with `AOC_YEAR` or in `aoc.toml`.
+#[must_use]
+pub fn get_year() -> Option<u16> {
+    config::get().year
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -105,6 +125,18 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
         cmd_args.push(year.to_string());
     }
 
+    // like for aoc-cli itself, a session in the environment takes precedence over the file.
+    let has_session_env = std::env::var_os("ADVENT_OF_CODE_SESSION").is_some();
+
+    if let Some(session_file) = config::get()
+        .session_file
+        .as_ref()
+        .filter(|_| !has_session_env)
+    {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_file.to_string_lossy().into_owned());
+    }
+
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
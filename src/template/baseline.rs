@@ -0,0 +1,183 @@
+/// Module that stores named benchmark baselines and compares timings against them.
+/// Baselines live in `target/baselines/<name>.csv`, similar to how criterion stores them.
+use std::path::PathBuf;
+use std::{fs, io};
+
+use crate::template::readme_benchmarks::Timings;
+use crate::template::report::{self, Error};
+use crate::template::{config, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Returns `true` if a baseline name stays inside the baselines directory.
+#[must_use]
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..")
+}
+
+fn get_path_for_baseline(name: &str) -> Result<PathBuf, Error> {
+    if !is_valid_name(name) {
+        return Err(Error::IO(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid baseline name \"{name}\""),
+        )));
+    }
+
+    Ok(config::target_dir()
+        .join("baselines")
+        .join(format!("{name}.csv")))
+}
+
+pub fn save(name: &str, timings: &[Timings]) -> Result<(), Error> {
+    let path = get_path_for_baseline(name)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, report::to_csv(timings))?;
+    Ok(())
+}
+
+pub fn load(name: &str) -> Result<Vec<Timings>, Error> {
+    let csv = fs::read_to_string(get_path_for_baseline(name)?)?;
+    report::from_csv(&csv)
+}
+
+fn format_change(before: f64, after: f64) -> String {
+    if after <= 0.0 || before <= 0.0 {
+        return "-".into();
+    }
+
+    let ratio = before / after;
+
+    if ratio >= 1.0 {
+        format!("{ratio:.2}x faster")
+    } else {
+        format!("{:.2}x slower", 1.0 / ratio)
+    }
+}
+
+/// Parts that were timed in both the current run and the baseline.
+struct Comparison {
+    lines: Vec<String>,
+    /// Sums of the compared parts, in nanoseconds.
+    total: f64,
+    base_total: f64,
+}
+
+fn compare(current: &[Timings], baseline: &[Timings]) -> Comparison {
+    let mut comparison = Comparison {
+        lines: vec![],
+        total: 0.0,
+        base_total: 0.0,
+    };
+
+    for timing in current {
+        let Some(base) = baseline.iter().find(|b| b.day == timing.day) else {
+            continue;
+        };
+
+        let parts = [
+            (
+                1,
+                &timing.part_1,
+                timing.part_1_nanos,
+                &base.part_1,
+                base.part_1_nanos,
+            ),
+            (
+                2,
+                &timing.part_2,
+                timing.part_2_nanos,
+                &base.part_2,
+                base.part_2_nanos,
+            ),
+        ];
+
+        for (part, time, nanos, base_time, base_nanos) in parts {
+            if let (Some(time), Some(nanos), Some(base_time), Some(base_nanos)) =
+                (time, nanos, base_time, base_nanos)
+            {
+                comparison.lines.push(format!(
+                    "Day {} Part {part}: {base_time} -> {time} ({})",
+                    timing.day,
+                    format_change(base_nanos, nanos)
+                ));
+                comparison.total += nanos;
+                comparison.base_total += base_nanos;
+            }
+        }
+    }
+
+    comparison
+}
+
+/// Prints the per-part speedup or slowdown of `current` against the baseline `name`.
+/// The total only covers parts that are in both, so partial runs are not skewed.
+pub fn print_comparison(name: &str, current: &[Timings], baseline: &[Timings]) {
+    println!("\n{ANSI_BOLD}Baseline \"{name}\"{ANSI_RESET}");
+
+    let comparison = compare(current, baseline);
+
+    if comparison.lines.is_empty() {
+        println!("{ANSI_ITALIC}No parts in common with the baseline.{ANSI_RESET}");
+        return;
+    }
+
+    for line in &comparison.lines {
+        println!("{line}");
+    }
+
+    println!(
+        "{ANSI_BOLD}Total:{ANSI_RESET} {} {ANSI_ITALIC}({} parts compared){ANSI_RESET}",
+        format_change(comparison.base_total, comparison.total),
+        comparison.lines.len()
+    );
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, format_change, is_valid_name, Timings};
+    use crate::day;
+
+    fn timing(part_1: (&str, f64), part_2: Option<(&str, f64)>) -> Timings {
+        Timings {
+            day: day!(5),
+            part_1: Some(part_1.0.into()),
+            part_2: part_2.map(|p| p.0.into()),
+            part_1_nanos: Some(part_1.1),
+            part_2_nanos: part_2.map(|p| p.1),
+            total_nanos: part_1.1 + part_2.map_or(0.0, |p| p.1),
+            threads: None,
+        }
+    }
+
+    #[test]
+    fn formats_speedup_and_slowdown() {
+        assert_eq!(format_change(200.0, 100.0), "2.00x faster");
+        assert_eq!(format_change(100.0, 400.0), "4.00x slower");
+        assert_eq!(format_change(0.0, 400.0), "-");
+    }
+
+    #[test]
+    fn compares_parts_present_in_both() {
+        let baseline = vec![timing(("2.0ms", 2e+6), None)];
+        let current = vec![timing(("1.0ms", 1e+6), Some(("5.0ms", 5e+6)))];
+        let comparison = compare(&current, &baseline);
+        assert_eq!(
+            comparison.lines,
+            vec!["Day 05 Part 1: 2.0ms -> 1.0ms (2.00x faster)"]
+        );
+        // part two is not in the baseline, so it does not count towards the total.
+        assert_eq!(comparison.total, 1e+6);
+        assert_eq!(comparison.base_total, 2e+6);
+    }
+
+    #[test]
+    fn rejects_names_outside_of_the_directory() {
+        assert!(is_valid_name("main"));
+        assert!(is_valid_name("before-refactor.2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../main"));
+        assert!(!is_valid_name("a/b"));
+        assert!(!is_valid_name("a\\b"));
+        assert!(!is_valid_name(".."));
+    }
+}
@@ -0,0 +1,98 @@
+/// Date helpers for the Advent of Code calendar. Puzzles unlock at midnight EST (UTC-5).
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Day;
+
+const UNLOCK_OFFSET_SECS: i64 = 5 * 60 * 60;
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+/// See: <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+#[must_use]
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Unix timestamp at which the puzzle of `day` in December of `year` unlocks.
+#[must_use]
+pub fn unlock_timestamp(year: u16, day: Day) -> i64 {
+    days_from_civil(i64::from(year), 12, u32::from(day.into_inner())) * 86_400 + UNLOCK_OFFSET_SECS
+}
+
+/// The most recently unlocked day of `year` at `timestamp`, if any day is unlocked yet.
+#[must_use]
+pub fn latest_unlocked(year: u16, timestamp: i64) -> Option<Day> {
+    crate::all_days()
+        .take_while(|day| unlock_timestamp(year, *day) <= timestamp)
+        .last()
+}
+
+/// Current unix timestamp in seconds.
+#[must_use]
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+}
+
+/// Formats a number of seconds as `hh:mm:ss`, prefixed with the number of days if there are any.
+#[must_use]
+pub fn format_duration(secs: i64) -> String {
+    let sign = if secs < 0 { "-" } else { "" };
+    let secs = secs.unsigned_abs();
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{sign}{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{sign}{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{days_from_civil, format_duration, latest_unlocked, unlock_timestamp};
+    use crate::day;
+
+    #[test]
+    fn computes_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2023, 12, 1), 19_692);
+    }
+
+    #[test]
+    fn computes_unlock() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_timestamp(2023, day!(1)), 1_701_406_800);
+        assert_eq!(
+            unlock_timestamp(2023, day!(25)) - unlock_timestamp(2023, day!(1)),
+            24 * 86_400
+        );
+    }
+
+    #[test]
+    fn finds_latest_unlocked() {
+        let unlock = unlock_timestamp(2023, day!(5));
+        assert_eq!(latest_unlocked(2023, unlock - 1), Some(day!(4)));
+        assert_eq!(latest_unlocked(2023, unlock), Some(day!(5)));
+        assert_eq!(
+            latest_unlocked(2023, unlock_timestamp(2023, day!(1)) - 1),
+            None
+        );
+        assert_eq!(latest_unlocked(2022, unlock), Some(day!(25)));
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(3725), "01:02:05");
+        assert_eq!(format_duration(90_061), "1d 01:01:01");
+        assert_eq!(format_duration(-61), "-00:01:01");
+    }
+}
@@ -1,38 +1,116 @@
-use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{io, process};
 
 use crate::template::{
+    baseline,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    report, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySelection};
+
+/// Flags of the `all` command.
+#[derive(Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub report: Option<PathBuf>,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
+    pub threads: Option<usize>,
+    pub timeout: Option<Duration>,
+    /// Only run this part of every day.
+    pub part: Option<u8>,
+    /// Only run these days instead of all of them.
+    pub days: Option<DaySelection>,
+}
+
+pub fn handle(options: &Options) {
+    let is_release = options.release;
+    let is_timed = options.time;
+    let report_dir = options.report.as_deref();
+    let save_baseline = options.save_baseline.as_deref();
+    let compare_baseline = options.baseline.as_deref();
+
+    if let Some(name) = [save_baseline, compare_baseline]
+        .into_iter()
+        .flatten()
+        .find(|name| !baseline::is_valid_name(name))
+    {
+        eprintln!("Invalid baseline name \"{name}\", it must not contain `/`, `\\` or `..`.");
+        process::exit(1);
+    }
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let mut summary = Summary::default();
+
+    let days = options.days.clone().unwrap_or_else(DaySelection::all);
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    days.iter().enumerate().for_each(|(i, day)| {
+        if i > 0 {
             println!();
         }
 
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let run = match child_commands::run_solution(day, options) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("Failed to run day {day}: {e:?}");
+                child_commands::Run::failed(None)
+            }
+        };
 
-        if output.is_empty() {
+        if run.output.is_empty() && run.status == Status::Success {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let val = child_commands::parse_exec_time(&run.output, day);
             timings.push(val);
         }
+
+        summary.add(day, &run, options.part);
     });
 
+    summary.print();
+
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
+        if let Some(dir) = report_dir {
+            match report::write(dir, &timings, total_millis) {
+                Ok(()) => println!("Successfully wrote benchmark report to {dir:?}."),
+                Err(_) => {
+                    eprintln!("Failed to write benchmark report.");
+                }
+            }
+        }
+
+        if let Some(name) = compare_baseline {
+            match baseline::load(name) {
+                Ok(base) => baseline::print_comparison(name, &timings, &base),
+                Err(_) => {
+                    eprintln!("Failed to load baseline \"{name}\".");
+                }
+            }
+        }
+
+        if let Some(name) = save_baseline {
+            match baseline::save(name, &timings) {
+                Ok(()) => println!("Successfully saved baseline \"{name}\"."),
+                Err(_) => {
+                    eprintln!("Failed to save baseline \"{name}\".");
+                }
+            }
+        }
+
+        // working with baselines, single parts or some days should not touch the committed benchmarks.
+        let uses_baseline = save_baseline.is_some() || compare_baseline.is_some();
+        let is_partial = options.part.is_some() || options.days.is_some();
+
+        if is_release && !uses_baseline && !is_partial {
             match readme_benchmarks::update(timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
@@ -40,6 +118,117 @@ pub fn handle(is_release: bool, is_timed: bool) {
                 }
             }
         }
+    } else if report_dir.is_some() || save_baseline.is_some() || compare_baseline.is_some() {
+        eprintln!("The `--report`, `--save-baseline` and `--baseline` options require `--time`.");
+    }
+
+    if summary.has_failures() {
+        process::exit(1);
+    }
+}
+
+/// How the solution binary of a day finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    /// The binary did not build or exited with an error, e.g. because it panicked.
+    Failed {
+        code: Option<i32>,
+        panic: Option<String>,
+    },
+    /// The binary was killed after exceeding the `--timeout`.
+    TimedOut(Duration),
+}
+
+/// The outcome of a single part, as printed by the runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartState {
+    Solved,
+    Unsolved,
+    Failed(String),
+    /// The part printed nothing, e.g. because the binary stopped before it ran.
+    Missing,
+}
+
+/// Tallies the outcome of all parts of an `all` run.
+#[derive(Debug, Default)]
+struct Summary {
+    solved: usize,
+    unsolved: usize,
+    failed: usize,
+    timed_out: usize,
+    failures: Vec<String>,
+}
+
+impl Summary {
+    fn add(&mut self, day: Day, run: &child_commands::Run, only_part: Option<u8>) {
+        // days that have not been scaffolded yet have no output at all.
+        if run.output.is_empty() && run.status == Status::Success {
+            self.unsolved += if only_part.is_some() { 1 } else { 2 };
+            return;
+        }
+
+        let failures = self.failures.len();
+
+        for (i, state) in child_commands::parse_parts(&run.output).iter().enumerate() {
+            let part = i + 1;
+            if only_part.is_some_and(|p| usize::from(p) != part) {
+                continue;
+            }
+
+            match (state, &run.status) {
+                (PartState::Solved, _) => self.solved += 1,
+                (PartState::Unsolved, _) | (PartState::Missing, Status::Success) => {
+                    self.unsolved += 1;
+                }
+                (PartState::Failed(error), _) => {
+                    self.failed += 1;
+                    self.failures
+                        .push(format!("Day {day} part {part}: error: {error}"));
+                }
+                (PartState::Missing, Status::TimedOut(_)) => self.timed_out += 1,
+                (PartState::Missing, Status::Failed { .. }) => self.failed += 1,
+            }
+        }
+
+        let has_part_errors = self.failures.len() > failures;
+
+        match &run.status {
+            Status::Success => {}
+            // parts that returned an error already explain the exit status.
+            Status::Failed { panic: None, .. } if has_part_errors => {}
+            Status::TimedOut(timeout) => self
+                .failures
+                .push(format!("Day {day}: timed out after {timeout:?}")),
+            Status::Failed {
+                panic: Some(panic), ..
+            } => self.failures.push(format!("Day {day}: {panic}")),
+            Status::Failed {
+                code: Some(code),
+                panic: None,
+            } => self
+                .failures
+                .push(format!("Day {day}: exited with status {code}")),
+            Status::Failed {
+                code: None,
+                panic: None,
+            } => self.failures.push(format!("Day {day}: failed to run")),
+        }
+    }
+
+    fn has_failures(&self) -> bool {
+        self.failed > 0 || self.timed_out > 0
+    }
+
+    fn print(&self) {
+        println!(
+            "\n{ANSI_BOLD}Summary:{ANSI_RESET} {} solved, {} unsolved, {} failed, {} timed out",
+            self.solved, self.unsolved, self.failed, self.timed_out
+        );
+
+        for failure in &self.failures {
+            println!("  {failure}");
+        }
     }
 }
 
@@ -63,40 +252,98 @@ pub fn get_path_for_bin(day: Day) -> String {
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
+pub(crate) mod child_commands {
+    use super::{get_path_for_bin, Error, Options, PartState, Status};
+    use crate::template::json;
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Output and exit status of a solution bin.
+    #[derive(Debug)]
+    pub struct Run {
+        pub output: Vec<String>,
+        pub status: Status,
+    }
+
+    impl Run {
+        pub fn failed(code: Option<i32>) -> Self {
+            Self {
+                output: vec![],
+                status: Status::Failed { code, panic: None },
+            }
+        }
+    }
+
+    /// Run the solution bin for a given day.
+    ///
+    /// The bin is built first and then executed directly, so that a `timeout` only covers the
+    /// solution itself and a timed out solution can be killed.
+    pub fn run_solution(day: Day, options: &Options) -> Result<Run, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(Run {
+                output: vec![],
+                status: Status::Success,
+            });
         }
 
         let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let threads = options.threads.map(|t| t.to_string());
+        let part = options.part.map(|p| p.to_string());
+        let mut build_args = vec![
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+            "--bin",
+            &day_padded,
+        ];
+
+        if options.release {
+            build_args.push("--release");
+        }
 
-        if is_release {
-            args.push("--release");
+        // compiler messages are rendered to stderr, stdout only carries the JSON messages.
+        let build = Command::new("cargo")
+            .args(&build_args)
+            .stderr(Stdio::inherit())
+            .output()?;
+        if !build.status.success() {
+            return Ok(Run::failed(build.status.code()));
         }
 
-        if is_timed {
+        let messages = String::from_utf8_lossy(&build.stdout);
+        let Some(executable) = find_executable(&messages, &day_padded) else {
+            eprintln!("cargo did not report an executable for bin \"{day_padded}\".");
+            return Ok(Run::failed(None));
+        };
+
+        let mut args = vec![];
+
+        if options.time {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(threads) = &threads {
+            args.push("--threads");
+            args.push(threads);
+        }
+
+        if let Some(part) = &part {
+            args.push("--part");
+            args.push(part);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -105,24 +352,132 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stderr_thread = thread::spawn(move || {
+            stderr
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| eprintln!("{line}"))
+                .collect::<Vec<_>>()
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stdout_thread = thread::spawn(move || {
+            stdout
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| println!("{line}"))
+                .collect::<Vec<_>>()
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let start = Instant::now();
+        let exit_status = loop {
+            if let Some(exit_status) = cmd.try_wait()? {
+                break Some(exit_status);
+            }
+
+            if options
+                .timeout
+                .is_some_and(|timeout| start.elapsed() > timeout)
+            {
+                cmd.kill()?;
+                cmd.wait()?;
+                break None;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        let output = stdout_thread.join().unwrap_or_default();
+        let errors = stderr_thread.join().unwrap_or_default();
+
+        let status = match (exit_status, options.timeout) {
+            (None, Some(timeout)) => Status::TimedOut(timeout),
+            (Some(exit_status), _) if exit_status.success() => Status::Success,
+            (exit_status, _) => Status::Failed {
+                code: exit_status.and_then(|s| s.code()),
+                panic: parse_panic(&errors),
+            },
+        };
+
+        Ok(Run { output, status })
+    }
+
+    /// Finds the executable of `bin` in the JSON messages of `cargo build`, so custom target
+    /// directories, target triples and profiles are respected.
+    pub fn find_executable(messages: &str, bin: &str) -> Option<PathBuf> {
+        messages
+            .lines()
+            .filter_map(|line| json::parse(line).ok())
+            .filter(|message| {
+                message.get("reason").and_then(json::Value::as_str) == Some("compiler-artifact")
+            })
+            .filter(|message| {
+                message
+                    .get("target")
+                    .and_then(|target| target.get("name"))
+                    .and_then(json::Value::as_str)
+                    == Some(bin)
+            })
+            .find_map(|message| {
+                message
+                    .get("executable")
+                    .and_then(json::Value::as_str)
+                    .map(PathBuf::from)
+            })
+    }
+
+    /// Extracts the location and message of a panic from the stderr of a solution bin.
+    pub fn parse_panic(errors: &[String]) -> Option<String> {
+        let start = errors.iter().position(|l| l.contains(" panicked at "))?;
+        let location = errors[start]
+            .split(" panicked at ")
+            .nth(1)?
+            .trim_end_matches(':');
+
+        let message = errors[start + 1..]
+            .iter()
+            .take_while(|l| {
+                !l.is_empty() && !l.starts_with("note:") && !l.starts_with("stack backtrace:")
+            })
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if message.is_empty() {
+            Some(format!("panicked at {location}"))
+        } else {
+            Some(format!("panicked at {location}: {message}"))
         }
+    }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    /// Reads the state of both parts from the output of a solution bin.
+    pub fn parse_parts(output: &[String]) -> [PartState; 2] {
+        let mut parts = [PartState::Missing, PartState::Missing];
+
+        for line in output {
+            // intermediate results are overwritten with a carriage return.
+            let line = line.rsplit('\r').next().unwrap_or(line);
+
+            let Some((part, result)) = line.split_once(": ") else {
+                continue;
+            };
+
+            let index = match part {
+                "Part 1" => 0,
+                "Part 2" => 1,
+                _ => continue,
+            };
+
+            let result = result.trim();
+            parts[index] = if result.starts_with('✖') {
+                PartState::Unsolved
+            } else if let Some(error) = result.strip_prefix("⚠ error: ") {
+                PartState::Failed(error.to_string())
+            } else {
+                PartState::Solved
+            };
+        }
 
-        Ok(output)
+        parts
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
@@ -130,7 +485,10 @@ mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            part_1_nanos: None,
+            part_2_nanos: None,
             total_nanos: 0_f64,
+            threads: None,
         };
 
         output
@@ -145,14 +503,20 @@ mod child_commands {
                     return None;
                 };
 
+                if let Some(threads) = parse_threads(l) {
+                    timings.threads = Some(threads);
+                }
+
                 let part = l.split(':').next()?;
                 Some((part, timing_str, nanos))
             })
             .for_each(|(part, timing_str, nanos)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_nanos = Some(nanos);
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_nanos = Some(nanos);
                 }
 
                 timings.total_nanos += nanos;
@@ -161,6 +525,15 @@ mod child_commands {
         timings
     }
 
+    fn parse_threads(line: &str) -> Option<usize> {
+        line.split(" samples) [")
+            .nth(1)?
+            .split(" threads]")
+            .next()?
+            .parse()
+            .ok()
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -171,7 +544,7 @@ mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -202,9 +575,29 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{find_executable, parse_exec_time, parse_panic, parse_parts};
+        use std::path::PathBuf;
 
         use crate::day;
+        use crate::template::commands::all::PartState;
+
+        #[test]
+        fn test_find_executable() {
+            let messages = [
+                r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code"},"executable":null}"#,
+                r#"{"reason":"compiler-artifact","target":{"name":"05"},"executable":"/tmp/build/x86_64-unknown-linux-gnu/bench/05"}"#,
+                r#"{"reason":"build-finished","success":true}"#,
+            ]
+            .join("\n");
+
+            assert_eq!(
+                find_executable(&messages, "05"),
+                Some(PathBuf::from(
+                    "/tmp/build/x86_64-unknown-linux-gnu/bench/05"
+                ))
+            );
+            assert_eq!(find_executable(&messages, "06"), None);
+        }
 
         #[test]
         fn test_well_formed() {
@@ -216,9 +609,27 @@ mod child_commands {
                 ],
                 day!(1),
             );
+            assert_eq!(res.threads, None);
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 74.13_f64);
+            assert_approx_eq!(res.part_2_nanos.unwrap(), 74130000_f64);
+        }
+
+        #[test]
+        fn test_thread_count() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [8 threads]".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples) [8 threads]".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.threads, Some(8));
         }
 
         #[test]
@@ -250,5 +661,40 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_panic() {
+            let errors = [
+                "thread 'main' (1234) panicked at src/bin/03.rs:12:5:".into(),
+                "called `Option::unwrap()` on a `None` value".into(),
+                "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace"
+                    .into(),
+            ];
+            assert_eq!(
+                parse_panic(&errors).unwrap(),
+                "panicked at src/bin/03.rs:12:5: called `Option::unwrap()` on a `None` value"
+            );
+            assert_eq!(parse_panic(&["warning: unused".into()]), None);
+        }
+
+        #[test]
+        fn test_parts() {
+            let parts = parse_parts(&[
+                "Part 1: 42\rPart 1: 42 (1.0ms)".into(),
+                "Part 2: ⚠\rPart 2: ⚠ error: invalid number \"x\"".into(),
+                "  caused by: invalid digit found in string".into(),
+            ]);
+            assert_eq!(
+                parts,
+                [
+                    PartState::Solved,
+                    PartState::Failed("invalid number \"x\"".into())
+                ]
+            );
+            assert_eq!(
+                parse_parts(&["Part 1: ✖             ".into()]),
+                [PartState::Unsolved, PartState::Missing]
+            );
+        }
     }
 }
@@ -1,15 +1,41 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_cli::{self, AocCommandError};
+use crate::template::commands::for_each_day;
+use crate::{Day, DaySelection};
 use std::process;
+use std::thread;
+use std::time::Duration;
 
-pub fn handle(day: Day) {
+/// Pause between the downloads of a batch, to not send bursts of requests to the servers.
+const BATCH_DELAY: Duration = Duration::from_secs(5);
+
+pub fn handle(days: &DaySelection) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let mut failed = false;
+    let mut is_first = true;
+
+    // keep downloading the remaining days of a batch if one of them fails.
+    for_each_day(days, |day| {
+        if !is_first {
+            thread::sleep(BATCH_DELAY);
+        }
+        is_first = false;
+
+        if let Err(e) = try_handle(day) {
+            eprintln!("failed to call aoc-cli: {e}");
+            failed = true;
+        }
+    });
+
+    if failed {
         process::exit(1);
-    };
+    }
+}
+
+/// Downloads input and puzzle, returning the error instead of exiting so callers can retry.
+pub fn try_handle(day: Day) -> Result<(), AocCommandError> {
+    aoc_cli::download(day).map(|_| ())
 }
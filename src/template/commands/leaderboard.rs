@@ -0,0 +1,413 @@
+/// Shows the standings of a private leaderboard.
+///
+/// Leaderboards are fetched with `curl` from `AOC_LEADERBOARD_URL` (default: the adventofcode.com API),
+/// which may also point to a local stand-in, e.g. `file:///tmp/leaderboard.json`.
+/// Responses are cached in `target/leaderboard/` for 15 minutes, as requested by the API documentation.
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write as _};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, SystemTime};
+use std::{env, fs, io};
+
+use crate::template::calendar::{format_duration, unlock_timestamp};
+use crate::template::json::{self, Value};
+use crate::template::{aoc_cli, config, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+const DEFAULT_URL: &str = "https://adventofcode.com/{year}/leaderboard/private/view/{id}.json";
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSession,
+    Request(String),
+    Json(json::ParseError),
+    Format(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<json::ParseError> for Error {
+    fn from(e: json::ParseError) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session token, set ADVENT_OF_CODE_SESSION, create ~/.adventofcode.session or configure paths.session in aoc.toml"
+            ),
+            Error::Request(e) => write!(f, "request failed: {e}"),
+            Error::Json(e) => write!(f, "invalid JSON: {e}"),
+            Error::Format(e) => write!(f, "unexpected leaderboard format: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    /// Star timestamps of both parts, by day.
+    pub days: BTreeMap<u8, [Option<i64>; 2]>,
+}
+
+impl Member {
+    fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+pub fn handle(id: &str, file: Option<&Path>, year: Option<u16>) {
+    let Some(year) = year.or_else(aoc_cli::get_year) else {
+        eprintln!("No year configured. Set `year` in aoc.toml, AOC_YEAR or pass `--year <year>`.");
+        process::exit(1);
+    };
+
+    let members = match file {
+        Some(path) => fs::read_to_string(path)
+            .map_err(Error::from)
+            .and_then(|json| parse(&json)),
+        None => fetch_cached(id, year),
+    };
+
+    match members {
+        Ok(members) => print!("{}", render(&members, year)),
+        Err(e) => {
+            eprintln!("Failed to load leaderboard {id}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn get_url(id: &str, year: u16) -> String {
+    env::var("AOC_LEADERBOARD_URL")
+        .unwrap_or_else(|_| DEFAULT_URL.into())
+        .replace("{year}", &year.to_string())
+        .replace("{id}", id)
+}
+
+/// The cache is keyed by the URL, so switching to a stand-in server does not serve stale data.
+fn get_path_for_cache(id: &str, year: u16, url: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+
+    config::target_dir()
+        .join("leaderboard")
+        .join(format!("{year}-{id}-{:016x}.json", hasher.finish()))
+}
+
+/// Returns the cached leaderboard if it is recent enough, fetches and caches it otherwise.
+fn fetch_cached(id: &str, year: u16) -> Result<Vec<Member>, Error> {
+    let url = get_url(id, year);
+    let path = get_path_for_cache(id, year, &url);
+
+    let age = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+
+    if let Some(age) = age.filter(|age| *age < CACHE_TTL) {
+        println!(
+            "{ANSI_ITALIC}Using cached leaderboard from {} min ago.{ANSI_RESET}",
+            age.as_secs() / 60
+        );
+        return parse(&fs::read_to_string(path)?);
+    }
+
+    let json = fetch(&url)?;
+    // make sure the response is a leaderboard before it is cached.
+    let members = parse(&json)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, &json)?;
+
+    Ok(members)
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Some(session);
+    }
+
+    let path = match &config::get().session_file {
+        Some(path) => path.clone(),
+        None => {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            Path::new(&home).join(".adventofcode.session")
+        }
+    };
+
+    fs::read_to_string(path).ok()
+}
+
+fn fetch(url: &str) -> Result<String, Error> {
+    let mut cmd = Command::new("curl");
+    cmd.args(["--silent", "--show-error", "--fail", "--location"]);
+
+    // local stand-ins don't need a session.
+    let session = if url.starts_with("http") {
+        Some(get_session().ok_or(Error::MissingSession)?)
+    } else {
+        None
+    };
+
+    // the session is passed as a config on stdin to keep it out of the process list.
+    if session.is_some() {
+        cmd.args(["--config", "-"]).stdin(Stdio::piped());
+    }
+
+    let request_error = |e: io::Error| Error::Request(format!("could not call curl: {e}"));
+
+    let mut child = cmd
+        .arg(url)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(request_error)?;
+
+    if let Some(session) = session {
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        writeln!(stdin, "cookie = \"session={}\"", session.trim()).map_err(request_error)?;
+    }
+
+    let output = child.wait_with_output().map_err(request_error)?;
+
+    if !output.status.success() {
+        return Err(Error::Request(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parses the members of a leaderboard response.
+pub fn parse(json: &str) -> Result<Vec<Member>, Error> {
+    let value = json::parse(json)?;
+    let missing = |field: &str| Error::Format(format!("missing \"{field}\""));
+
+    let members = value
+        .get("members")
+        .and_then(Value::as_object)
+        .ok_or_else(|| missing("members"))?;
+
+    members
+        .iter()
+        .map(|(id, member)| {
+            let mut days = BTreeMap::new();
+
+            for (day, levels) in member
+                .get("completion_day_level")
+                .and_then(Value::as_object)
+                .unwrap_or_default()
+            {
+                let day = day
+                    .parse()
+                    .map_err(|_| Error::Format(format!("invalid day \"{day}\"")))?;
+                let star = |part: &str| {
+                    levels
+                        .get(part)
+                        .and_then(|l| l.get("get_star_ts"))
+                        .and_then(Value::as_u64)
+                        .and_then(|ts| i64::try_from(ts).ok())
+                };
+                days.insert(day, [star("1"), star("2")]);
+            }
+
+            Ok(Member {
+                id: member
+                    .get("id")
+                    .and_then(Value::as_u64)
+                    .or_else(|| id.parse().ok())
+                    .ok_or_else(|| missing("id"))?,
+                name: member.get("name").and_then(Value::as_str).map(String::from),
+                local_score: member
+                    .get("local_score")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0),
+                stars: member.get("stars").and_then(Value::as_u64).unwrap_or(0),
+                days,
+            })
+        })
+        .collect()
+}
+
+/// Renders the standings, followed by star times and part deltas for every day with stars.
+#[must_use]
+pub fn render(members: &[Member], year: u16) -> String {
+    let mut members: Vec<&Member> = members.iter().collect();
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.id.cmp(&b.id))
+    });
+
+    let name_width = members
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+
+    let mut out = String::new();
+
+    let _ = writeln!(out, "{ANSI_BOLD}Standings {year}{ANSI_RESET}");
+    let _ = writeln!(
+        out,
+        "     {:<name_width$}              {:>25}",
+        "", "1111111111222222"
+    );
+    let _ = writeln!(
+        out,
+        "     {:<name_width$} Score Stars  1234567890123456789012345",
+        "Member"
+    );
+
+    for (rank, member) in members.iter().enumerate() {
+        let grid: String = (1..=25)
+            .map(|day| match member.days.get(&day) {
+                Some([Some(_), Some(_)]) => '★',
+                Some([Some(_), None]) => '☆',
+                _ => '·',
+            })
+            .collect();
+
+        let _ = writeln!(
+            out,
+            "{:>3}) {:<name_width$} {:>5} {:>5}  {grid}",
+            rank + 1,
+            member.display_name(),
+            member.local_score,
+            member.stars,
+        );
+    }
+
+    let days: Vec<u8> = (1..=25)
+        .filter(|day| members.iter().any(|m| m.days.contains_key(day)))
+        .collect();
+
+    for day in days {
+        let Some(unlock) = Day::new(day).map(|d| unlock_timestamp(year, d)) else {
+            continue;
+        };
+
+        let _ = writeln!(out, "\n{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        let _ = writeln!(
+            out,
+            "     {:<name_width$} {:>12} {:>12} {:>12}",
+            "Member", "Part 1", "Part 2", "Delta"
+        );
+
+        let mut finishers: Vec<(&Member, [Option<i64>; 2])> = members
+            .iter()
+            .filter_map(|m| Some((*m, *m.days.get(&day)?)))
+            .collect();
+        finishers.sort_by_key(|(m, [one, two])| (two.is_none(), *two, *one, m.id));
+
+        for (rank, (member, [one, two])) in finishers.iter().enumerate() {
+            let time = |ts: &Option<i64>| ts.map_or("-".into(), |ts| format_duration(ts - unlock));
+            let delta = match (one, two) {
+                (Some(one), Some(two)) => format!("+{}", format_duration(two - one)),
+                _ => "-".into(),
+            };
+
+            let _ = writeln!(
+                out,
+                "{:>3}) {:<name_width$} {:>12} {:>12} {:>12}",
+                rank + 1,
+                member.display_name(),
+                time(one),
+                time(two),
+                delta,
+            );
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get_path_for_cache, parse, render};
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 7, "global_score": 0,
+                "last_star_ts": 1701496800,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701407100, "star_index": 1},
+                        "2": {"get_star_ts": 1701407700, "star_index": 2}
+                    },
+                    "2": {"1": {"get_star_ts": 1701496800, "star_index": 3}}
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 1, "local_score": 2, "global_score": 0,
+                "last_star_ts": 1701410400,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1701410400, "star_index": 4}}
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_members() {
+        let members = parse(LEADERBOARD).unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].name.as_deref(), Some("alice"));
+        assert_eq!(
+            members[0].days.get(&1),
+            Some(&[Some(1_701_407_100), Some(1_701_407_700)])
+        );
+        assert_eq!(members[1].name, None);
+        assert!(parse(r#"{"event": "2023"}"#).is_err());
+    }
+
+    #[test]
+    fn renders_standings_and_deltas() {
+        let out = render(&parse(LEADERBOARD).unwrap(), 2023);
+
+        assert!(out.contains("  1) alice                   7     3  ★☆·"));
+        assert!(out.contains("  2) (anonymous user #2)     2     1  ☆··"));
+        // day 1 unlocked at 1701406800.
+        assert!(out.contains("  1) alice                   00:05:00     00:15:00    +00:10:00"));
+        assert!(out.contains("  2) (anonymous user #2)     01:00:00            -            -"));
+    }
+
+    #[test]
+    fn caches_by_url() {
+        let path = |url| get_path_for_cache("42", 2023, url);
+        let real = path("https://adventofcode.com/2023/leaderboard/private/view/42.json");
+
+        assert_eq!(
+            real,
+            path("https://adventofcode.com/2023/leaderboard/private/view/42.json")
+        );
+        assert_ne!(real, path("http://localhost:8080/2023/42.json"));
+    }
+}
@@ -1,5 +1,27 @@
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::{Day, DaySelection};
+
 pub mod all;
 pub mod download;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stress;
+pub mod tui;
+pub mod wait;
+
+/// Calls `f` for every selected day, separated by day headers if there is more than one.
+pub fn for_each_day(days: &DaySelection, mut f: impl FnMut(Day)) {
+    for (i, day) in days.iter().enumerate() {
+        if days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+        }
+
+        f(day);
+    }
+}
@@ -1,16 +1,26 @@
 use std::process;
 
 use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::commands::for_each_day;
+use crate::DaySelection;
 
-pub fn handle(day: Day) {
+pub fn handle(days: &DaySelection) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let mut failed = false;
+
+    // keep reading the remaining days of a batch if one of them fails, like `download`.
+    for_each_day(days, |day| {
+        if let Err(e) = aoc_cli::read(day) {
+            eprintln!("failed to call aoc-cli: {e}");
+            failed = true;
+        };
+    });
+
+    if failed {
         process::exit(1);
-    };
+    }
 }
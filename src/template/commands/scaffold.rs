@@ -1,10 +1,14 @@
 use std::{
     fs::{File, OpenOptions},
     io::Write,
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::Day;
+use crate::template::commands::for_each_day;
+use crate::template::config::{self, display_path};
+use crate::template::crypt;
+use crate::{Day, DaySelection};
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
@@ -34,18 +38,46 @@ mod tests {
 }
 "#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+}
+
+/// Scaffolds every selected day. Batches skip days that already have a module.
+pub fn handle_batch(days: &DaySelection) {
+    if let Some(day) = days.single() {
+        handle(day);
+        return;
+    }
+
+    for_each_day(days, |day| {
+        if get_module_path(day).exists() {
+            println!("Skipped, module file \"src/bin/{day}.rs\" already exists.");
+        } else {
+            handle(day);
+        }
+    });
+}
+
+fn get_module_path(day: Day) -> PathBuf {
+    config::root()
+        .join("src")
+        .join("bin")
+        .join(format!("{day}.rs"))
 }
 
 pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let config = config::get();
+    let input_path = config.data_dir("inputs").join(format!("{day}.txt"));
+    let example_path = config.data_dir("examples").join(format!("{day}.txt"));
+    let module_path = get_module_path(day);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -61,7 +93,7 @@ pub fn handle(day: Day) {
             .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", display_path(&module_path));
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -69,19 +101,30 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    // an empty input would shadow an input that was committed encrypted.
+    if crypt::encrypted_path(&input_path).exists() {
+        println!(
+            "Skipped input file, \"{}\" is already committed encrypted.",
+            display_path(&input_path)
+        );
+    } else {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", display_path(&input_path));
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!(
+                "Created empty example file \"{}\"",
+                display_path(&example_path)
+            );
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -1,27 +1,108 @@
-use std::process::{Command, Stdio};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
 
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// Input a solution runs against instead of `data/inputs/<day>.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+    /// `data/examples/<day>.txt`, or `data/examples/<day>-<name>.txt` if a name is given.
+    Example(Option<String>),
+}
+
+/// Flags of the `solve` command that are forwarded to the solution binary.
+#[derive(Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub time: bool,
+    pub submit: Option<u8>,
+    pub compare: bool,
+    pub threads: Option<usize>,
+    pub speedup: bool,
+    pub trace: bool,
+    pub visualize: bool,
+    pub input: Option<Input>,
+    /// Only run this part.
+    pub part: Option<u8>,
+}
+
+pub fn handle(day: Day, options: &Options) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if release {
+    if options.release {
         cmd_args.push("--release".to_string());
     }
 
+    if options.trace {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("trace".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let (Some(submit_part), Some(part)) = (options.submit, options.part) {
+        if submit_part != part {
+            eprintln!("Can't submit part {submit_part} when only running part {part}.");
+            process::exit(1);
+        }
+    }
+
+    if let Some(part) = options.part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if let Some(submit_part) = options.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
+    if options.time {
         cmd_args.push("--time".to_string());
     }
 
+    if options.compare {
+        cmd_args.push("--compare".to_string());
+    }
+
+    if let Some(threads) = options.threads {
+        cmd_args.push("--threads".to_string());
+        cmd_args.push(threads.to_string());
+    }
+
+    if options.speedup {
+        cmd_args.push("--speedup".to_string());
+    }
+
+    if options.trace {
+        cmd_args.push("--trace".to_string());
+    }
+
+    if options.visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    match &options.input {
+        Some(Input::File(path)) => {
+            cmd_args.push("--input".to_string());
+            cmd_args.push(path.to_string_lossy().into_owned());
+        }
+        Some(Input::Stdin) => {
+            cmd_args.push("--input".to_string());
+            cmd_args.push("-".to_string());
+        }
+        Some(Input::Example(name)) => {
+            cmd_args.push("--example".to_string());
+            cmd_args.extend(name.clone());
+        }
+        None => {}
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
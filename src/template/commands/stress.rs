@@ -0,0 +1,24 @@
+use std::process::{Command, Stdio};
+
+use crate::Day;
+
+pub fn handle(day: Day, scale: u32, release: bool) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.push("--stress".to_string());
+    cmd_args.push(scale.to_string());
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
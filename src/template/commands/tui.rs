@@ -0,0 +1,798 @@
+/// Full-screen terminal dashboard to run, bench and test days and to read their puzzles.
+///
+/// Raw mode is toggled with termios on unix and console modes on Windows, everything else is
+/// plain ANSI escape codes.
+use std::collections::VecDeque;
+use std::io::{self, stdin, stdout, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+use std::{env, thread};
+
+use super::all::{
+    child_commands::{parse_exec_time, parse_parts},
+    get_path_for_bin, PartState,
+};
+use crate::template::readme_benchmarks::Timings;
+use crate::template::{config, crypt, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{all_days, Day};
+
+const ANSI_INVERT: &str = "\x1b[7m";
+const ANSI_CLEAR: &str = "\x1b[H\x1b[2J";
+
+pub fn handle(is_release: bool) {
+    let mut terminal = match Terminal::enter() {
+        Ok(terminal) => terminal,
+        Err(e) => {
+            eprintln!("Failed to set up terminal: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut app = App::new(is_release);
+
+    loop {
+        let (width, height) = terminal.size();
+        let _ = app.render(width, height);
+
+        let key = terminal.read_key();
+        app.message.clear();
+
+        match key {
+            Key::Char('q') if matches!(app.view, View::List) => break,
+            Key::Char('q') | Key::Escape => app.view = View::List,
+            Key::Up => app.move_by(-1, height),
+            Key::Down => app.move_by(1, height),
+            Key::PageUp => app.move_by(-(height as isize / 2), height),
+            Key::PageDown => app.move_by(height as isize / 2, height),
+            Key::Char('r') | Key::Enter => app.run(false),
+            Key::Char('b') => app.run(true),
+            Key::Char('t') => app.test(),
+            Key::Char('p') => app.show_file("Puzzle", &data_path("puzzles", app.day(), "md")),
+            Key::Char('o') => app.show_output(),
+            Key::Char('i') => {
+                let path = data_path("inputs", app.day(), "txt");
+                if is_present(&path) {
+                    match crypt::read_to_string(&path) {
+                        Ok(input) => {
+                            terminal.suspend();
+                            if let Err(e) = open_pager(&input) {
+                                app.message = format!("Failed to open pager: {e}");
+                            }
+                            terminal.resume();
+                        }
+                        Err(e) => app.message = format!("Could not read {}: {e}", path.display()),
+                    }
+                } else {
+                    app.message = format!("No input at {}.", path.display());
+                }
+            }
+            Key::Char(_) | Key::Other => {}
+        }
+    }
+}
+
+/// Restores the terminal when dropped.
+struct Terminal {
+    saved: sys::Mode,
+    input: Input,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        let terminal = Self {
+            saved: sys::get_mode()?,
+            input: Input::spawn(),
+        };
+        terminal.resume();
+        Ok(terminal)
+    }
+
+    /// Switches back to the normal screen, e.g. to hand the terminal to a pager.
+    fn suspend(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = stdout().flush();
+        let _ = sys::set_mode(&self.saved);
+    }
+
+    fn resume(&self) {
+        let _ = sys::set_mode(&sys::raw_mode(&self.saved));
+        print!("\x1b[?1049h\x1b[?25l");
+        let _ = stdout().flush();
+    }
+
+    /// Returns `(columns, rows)` of the terminal, by moving the cursor to the bottom right
+    /// corner and asking the terminal where it ended up.
+    fn size(&mut self) -> (usize, usize) {
+        print!("\x1b7\x1b[999;999H\x1b[6n\x1b8");
+        let _ = stdout().flush();
+
+        // keys that were pressed before the report arrived are kept for `read_key`.
+        let mut typed = vec![];
+        let mut report = vec![];
+        while let Some(byte) = self.input.receive(Some(REPORT_TIMEOUT)) {
+            if report.is_empty() && byte != 0x1b {
+                typed.push(byte);
+                continue;
+            }
+            report.push(byte);
+            if byte == b'R' {
+                break;
+            }
+        }
+        self.input.pending.extend(typed);
+
+        parse_cursor_report(&report)
+            // pseudo terminals may not report a size.
+            .filter(|&(cols, rows)| cols > 0 && rows > 0)
+            .unwrap_or((80, 24))
+    }
+
+    fn read_key(&mut self) -> Key {
+        parse_key(|timeout| self.input.next_byte(timeout))
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        self.suspend();
+    }
+}
+
+/// Time to wait for the rest of an escape sequence before a lone ESC is taken as the escape key.
+const ESCAPE_TIMEOUT: Duration = Duration::from_millis(30);
+
+/// Time to wait for the terminal to report the cursor position.
+const REPORT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Reads stdin on a background thread, so reads can time out.
+///
+/// The thread only reads a byte when one is requested, so no keys are taken from a pager
+/// while the dashboard is suspended.
+struct Input {
+    requests: Sender<()>,
+    bytes: Receiver<Option<u8>>,
+    /// A requested byte that has not been received yet, because the read timed out.
+    outstanding: bool,
+    pending: VecDeque<u8>,
+}
+
+impl Input {
+    fn spawn() -> Self {
+        let (requests, requested) = mpsc::channel::<()>();
+        let (sender, bytes) = mpsc::channel();
+
+        thread::spawn(move || {
+            for () in requested {
+                let mut byte = [0u8; 1];
+                let read = stdin().lock().read_exact(&mut byte).map(|()| byte[0]).ok();
+                if sender.send(read).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            requests,
+            bytes,
+            outstanding: false,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Returns the next key press byte, or `None` if the read timed out or stdin was closed.
+    fn next_byte(&mut self, timeout: Option<Duration>) -> Option<u8> {
+        self.pending.pop_front().or_else(|| self.receive(timeout))
+    }
+
+    /// Reads a byte from stdin, skipping the pending bytes.
+    fn receive(&mut self, timeout: Option<Duration>) -> Option<u8> {
+        if !self.outstanding {
+            self.requests.send(()).ok()?;
+            self.outstanding = true;
+        }
+
+        let received = match timeout {
+            Some(timeout) => self.bytes.recv_timeout(timeout).ok(),
+            None => self.bytes.recv().ok(),
+        };
+
+        // a timed out read stays outstanding, its byte is returned by the next call.
+        let byte = received?;
+        self.outstanding = false;
+        byte
+    }
+}
+
+/// Switches the terminal between cooked and raw mode without external programs.
+#[cfg(unix)]
+mod sys {
+    use std::io;
+    use std::os::raw::c_int;
+
+    /// A `struct termios`, kept opaque. It is smaller than this buffer on all unix platforms.
+    #[repr(C, align(8))]
+    #[derive(Clone, Copy)]
+    pub struct Mode([u8; 256]);
+
+    extern "C" {
+        fn tcgetattr(fd: c_int, termios: *mut Mode) -> c_int;
+        fn tcsetattr(fd: c_int, action: c_int, termios: *const Mode) -> c_int;
+        fn cfmakeraw(termios: *mut Mode);
+    }
+
+    const STDIN: c_int = 0;
+    const TCSANOW: c_int = 0;
+
+    pub fn get_mode() -> io::Result<Mode> {
+        let mut mode = Mode([0; 256]);
+        // SAFETY: `mode` is a valid, writable buffer that is larger than `struct termios`.
+        if unsafe { tcgetattr(STDIN, &mut mode) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(mode)
+    }
+
+    pub fn set_mode(mode: &Mode) -> io::Result<()> {
+        // SAFETY: `mode` was filled by `tcgetattr`.
+        if unsafe { tcsetattr(STDIN, TCSANOW, mode) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub fn raw_mode(mode: &Mode) -> Mode {
+        let mut raw = *mode;
+        // SAFETY: `raw` is a copy of a mode that was filled by `tcgetattr`.
+        unsafe { cfmakeraw(&mut raw) };
+        raw
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::ffi::c_void;
+    use std::io;
+
+    /// Console modes of stdin and stdout.
+    #[derive(Clone, Copy)]
+    pub struct Mode {
+        input: u32,
+        output: u32,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(handle: u32) -> *mut c_void;
+        fn GetConsoleMode(handle: *mut c_void, mode: *mut u32) -> i32;
+        fn SetConsoleMode(handle: *mut c_void, mode: u32) -> i32;
+    }
+
+    const STD_INPUT_HANDLE: u32 = -10_i32 as u32;
+    const STD_OUTPUT_HANDLE: u32 = -11_i32 as u32;
+    const ENABLE_PROCESSED_INPUT: u32 = 0x1;
+    const ENABLE_LINE_INPUT: u32 = 0x2;
+    const ENABLE_ECHO_INPUT: u32 = 0x4;
+    const ENABLE_VIRTUAL_TERMINAL_INPUT: u32 = 0x200;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x4;
+
+    fn console_mode(handle: u32) -> io::Result<u32> {
+        let mut mode = 0;
+        // SAFETY: `GetStdHandle` returns a handle or null, which `GetConsoleMode` rejects.
+        if unsafe { GetConsoleMode(GetStdHandle(handle), &mut mode) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(mode)
+    }
+
+    fn set_console_mode(handle: u32, mode: u32) -> io::Result<()> {
+        // SAFETY: see `console_mode`.
+        if unsafe { SetConsoleMode(GetStdHandle(handle), mode) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub fn get_mode() -> io::Result<Mode> {
+        Ok(Mode {
+            input: console_mode(STD_INPUT_HANDLE)?,
+            output: console_mode(STD_OUTPUT_HANDLE)?,
+        })
+    }
+
+    pub fn set_mode(mode: &Mode) -> io::Result<()> {
+        set_console_mode(STD_INPUT_HANDLE, mode.input)?;
+        set_console_mode(STD_OUTPUT_HANDLE, mode.output)
+    }
+
+    /// Delivers key presses as ANSI sequences and interprets them in the output.
+    pub fn raw_mode(mode: &Mode) -> Mode {
+        Mode {
+            input: (mode.input & !(ENABLE_PROCESSED_INPUT | ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT))
+                | ENABLE_VIRTUAL_TERMINAL_INPUT,
+            output: mode.output | ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod sys {
+    use std::io;
+
+    #[derive(Clone, Copy)]
+    pub struct Mode;
+
+    pub fn get_mode() -> io::Result<Mode> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "raw mode is not supported on this platform",
+        ))
+    }
+
+    pub fn set_mode(_mode: &Mode) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn raw_mode(mode: &Mode) -> Mode {
+        *mode
+    }
+}
+
+/// Parses a cursor position report `ESC [ <row> ; <col> R` into `(columns, rows)`.
+fn parse_cursor_report(report: &[u8]) -> Option<(usize, usize)> {
+    let report = std::str::from_utf8(report).ok()?;
+    let (rows, cols) = report
+        .strip_prefix("\x1b[")?
+        .strip_suffix('R')?
+        .split_once(';')?;
+    Some((cols.parse().ok()?, rows.parse().ok()?))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Key {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Enter,
+    Escape,
+    Char(char),
+    Other,
+}
+
+/// Parses a key press. `next` reads a byte, waiting at most for the given timeout.
+/// The rest of an escape sequence is read with a short timeout, so a lone ESC does not block.
+fn parse_key(mut next: impl FnMut(Option<Duration>) -> Option<u8>) -> Key {
+    let first = next(None);
+    let mut follow = || next(Some(ESCAPE_TIMEOUT));
+
+    match first {
+        // `ESC [ <code>` sequences of the arrow and page keys.
+        Some(0x1b) => match follow() {
+            None => Key::Escape,
+            Some(b'[') => match follow() {
+                Some(b'A') => Key::Up,
+                Some(b'B') => Key::Down,
+                Some(code @ (b'5' | b'6')) => match (code, follow()) {
+                    (b'5', Some(b'~')) => Key::PageUp,
+                    (b'6', Some(b'~')) => Key::PageDown,
+                    _ => Key::Other,
+                },
+                _ => Key::Other,
+            },
+            Some(_) => Key::Other,
+        },
+        Some(b'\r' | b'\n') => Key::Enter,
+        Some(b'k') => Key::Up,
+        Some(b'j') => Key::Down,
+        // ctrl-c is not turned into a signal in raw mode.
+        Some(3) | None => Key::Char('q'),
+        Some(b) => Key::Char(char::from(b)),
+    }
+}
+
+fn data_path(folder: &str, day: Day, extension: &str) -> PathBuf {
+    config::get()
+        .data_dir(folder)
+        .join(format!("{day}.{extension}"))
+}
+
+/// Returns `true` if a data file exists in plaintext or as its encrypted counterpart.
+fn is_present(path: &Path) -> bool {
+    path.exists() || crypt::encrypted_path(path).exists()
+}
+
+/// Shows a text in the pager, piped through stdin so decrypted inputs never touch the disk.
+fn open_pager(content: &str) -> io::Result<()> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".into());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    // the pager may quit before it read everything, which is not an error.
+    let _ = child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(content.as_bytes());
+    child.wait()?;
+    Ok(())
+}
+
+/// Results of the last run of a day in this session.
+#[derive(Default)]
+struct DayState {
+    parts: Option<[PartState; 2]>,
+    timings: Option<Timings>,
+    output: Vec<String>,
+}
+
+enum View {
+    List,
+    Text {
+        title: String,
+        lines: Vec<String>,
+        scroll: usize,
+    },
+}
+
+struct App {
+    days: Vec<Day>,
+    states: Vec<DayState>,
+    selected: usize,
+    view: View,
+    message: String,
+    is_release: bool,
+}
+
+impl App {
+    fn new(is_release: bool) -> Self {
+        let days: Vec<Day> = all_days().collect();
+        let states = days.iter().map(|_| DayState::default()).collect();
+
+        Self {
+            days,
+            states,
+            selected: 0,
+            view: View::List,
+            message: String::new(),
+            is_release,
+        }
+    }
+
+    fn day(&self) -> Day {
+        self.days[self.selected]
+    }
+
+    fn move_by(&mut self, delta: isize, height: usize) {
+        match &mut self.view {
+            View::List => {
+                self.selected = self
+                    .selected
+                    .saturating_add_signed(delta)
+                    .min(self.days.len() - 1);
+            }
+            View::Text { lines, scroll, .. } => {
+                let max = lines.len().saturating_sub(height.saturating_sub(2));
+                *scroll = scroll.saturating_add_signed(delta).min(max);
+            }
+        }
+    }
+
+    /// Shows a message in the footer while a command blocks the loop.
+    fn status(message: &str) {
+        print!("\x1b[999;1H\r\x1b[2K{ANSI_ITALIC}{message}{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    /// Runs the selected day, optionally benchmarked on a release build.
+    fn run(&mut self, bench: bool) {
+        let day = self.day();
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            self.message = format!("Day {day} is not scaffolded yet.");
+            return;
+        }
+
+        Self::status(&format!("Running day {day}..."));
+
+        let day_padded = day.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if self.is_release || bench {
+            args.push("--release");
+        }
+        args.push("--");
+        if bench {
+            args.push("--time");
+        }
+
+        let Some(output) = capture(&args) else {
+            self.message = "Failed to run cargo.".into();
+            return;
+        };
+
+        let stdout = output
+            .iter()
+            .take_while(|l| *l != STDERR_SEPARATOR)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let parts = parse_parts(&stdout);
+        self.message = format!(
+            "Day {day}: {}, {}",
+            describe(&parts[0]),
+            describe(&parts[1])
+        );
+
+        let state = &mut self.states[self.selected];
+        state.parts = Some(parts);
+        state.timings = bench.then(|| parse_exec_time(&stdout, day));
+        state.output = output;
+    }
+
+    /// Runs the tests of the selected day and shows their output.
+    fn test(&mut self) {
+        let day = self.day();
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            self.message = format!("Day {day} is not scaffolded yet.");
+            return;
+        }
+
+        Self::status(&format!("Testing day {day}..."));
+
+        let day_padded = day.to_string();
+        let Some(output) = capture(&["test", "--quiet", "--bin", &day_padded]) else {
+            self.message = "Failed to run cargo.".into();
+            return;
+        };
+
+        self.view = View::Text {
+            title: format!("Tests of day {day}"),
+            lines: output,
+            scroll: 0,
+        };
+    }
+
+    fn show_file(&mut self, title: &str, path: &Path) {
+        match crypt::read_to_string(path) {
+            Ok(content) => {
+                self.view = View::Text {
+                    title: format!("{title} of day {}", self.day()),
+                    lines: content.lines().map(String::from).collect(),
+                    scroll: 0,
+                };
+            }
+            Err(_) => self.message = format!("Could not read {}.", path.display()),
+        }
+    }
+
+    fn show_output(&mut self) {
+        let day = self.day();
+        let output = &self.states[self.selected].output;
+
+        if output.is_empty() {
+            self.message = format!("Day {day} has not been run yet.");
+            return;
+        }
+
+        self.view = View::Text {
+            title: format!("Last output of day {day}"),
+            lines: output.clone(),
+            scroll: 0,
+        };
+    }
+
+    fn render(&self, width: usize, height: usize) -> io::Result<()> {
+        let mut out = String::from(ANSI_CLEAR);
+
+        let (title, body, keys) = match &self.view {
+            View::List => (
+                "Advent of Code".to_string(),
+                self.list_lines(height.saturating_sub(3)),
+                "↑/↓ select  r run  b bench  t test  p puzzle  o output  i input  q quit",
+            ),
+            View::Text {
+                title,
+                lines,
+                scroll,
+            } => (
+                title.clone(),
+                lines
+                    .iter()
+                    .skip(*scroll)
+                    .take(height.saturating_sub(2))
+                    .cloned()
+                    .collect(),
+                "↑/↓ scroll  PgUp/PgDn page  q back",
+            ),
+        };
+
+        out.push_str(&format!(
+            "{ANSI_BOLD}{}{ANSI_RESET}\r\n",
+            truncate(&title, width)
+        ));
+        for line in &body {
+            out.push_str(&truncate(line, width));
+            out.push_str(&format!("{ANSI_RESET}\r\n"));
+        }
+
+        let footer = if self.message.is_empty() {
+            keys
+        } else {
+            &self.message
+        };
+        out.push_str(&format!(
+            "\x1b[{height};1H{ANSI_ITALIC}{}{ANSI_RESET}",
+            truncate(footer, width)
+        ));
+
+        let mut stdout = stdout().lock();
+        stdout.write_all(out.as_bytes())?;
+        stdout.flush()
+    }
+
+    fn list_lines(&self, rows: usize) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{ANSI_BOLD}{:<5} {:<5} {:<5} {:<6} {:<14} {:<14}{ANSI_RESET}",
+            "Day", "Code", "Input", "Puzzle", "Part 1", "Part 2"
+        )];
+
+        // keep the selection in view on small terminals.
+        let offset = (self.selected + 2).saturating_sub(rows);
+
+        for (i, (day, state)) in self.days.iter().zip(&self.states).enumerate().skip(offset) {
+            let exists = |path: PathBuf| if is_present(&path) { "✔" } else { "·" };
+
+            let part = |index: usize| {
+                let state_str = state
+                    .parts
+                    .as_ref()
+                    .map_or("·", |parts| symbol(&parts[index]));
+                let timing = state.timings.as_ref().and_then(|t| match index {
+                    0 => t.part_1.clone(),
+                    _ => t.part_2.clone(),
+                });
+                match timing {
+                    Some(timing) => format!("{state_str} {timing}"),
+                    None => state_str.to_string(),
+                }
+            };
+
+            let line = format!(
+                "{:<5} {:<5} {:<5} {:<6} {:<14} {:<14}",
+                day.to_string(),
+                exists(PathBuf::from(get_path_for_bin(*day))),
+                exists(data_path("inputs", *day, "txt")),
+                exists(data_path("puzzles", *day, "md")),
+                part(0),
+                part(1),
+            );
+
+            if i == self.selected {
+                lines.push(format!("{ANSI_INVERT}{line}{ANSI_RESET}"));
+            } else {
+                lines.push(line);
+            }
+        }
+
+        lines
+    }
+}
+
+const STDERR_SEPARATOR: &str = "--- stderr ---";
+
+/// Runs cargo and returns its stdout lines, followed by its stderr lines if there are any.
+fn capture(args: &[&str]) -> Option<Vec<String>> {
+    let output = Command::new("cargo")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+
+    let mut lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        // intermediate results are overwritten with a carriage return.
+        .map(|l| l.rsplit('\r').next().unwrap_or(l).to_string())
+        .collect();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        lines.push(STDERR_SEPARATOR.into());
+        lines.extend(stderr.lines().map(String::from));
+    }
+
+    Some(lines)
+}
+
+fn symbol(state: &PartState) -> &'static str {
+    match state {
+        PartState::Solved => "✔",
+        PartState::Unsolved => "✖",
+        PartState::Failed(_) => "⚠",
+        PartState::Missing => "?",
+    }
+}
+
+fn describe(state: &PartState) -> &'static str {
+    match state {
+        PartState::Solved => "solved",
+        PartState::Unsolved => "unsolved",
+        PartState::Failed(_) => "failed",
+        PartState::Missing => "no output",
+    }
+}
+
+/// Cuts a line to `width` visible characters, skipping over ANSI escape sequences.
+fn truncate(line: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut visible = 0;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            out.push(c);
+            for c in chars.by_ref() {
+                out.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else if visible < width {
+            // tabs would move the cursor past the cut.
+            if c == '\t' {
+                out.push(' ');
+            } else {
+                out.push(c);
+            }
+            visible += 1;
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cursor_report, parse_key, truncate, Key};
+
+    /// Parses a key from `bytes`, reads past their end time out.
+    fn key(bytes: &[u8]) -> Key {
+        let mut bytes = bytes.iter().copied();
+        parse_key(|_| bytes.next())
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(key(b"\x1b[A"), Key::Up);
+        assert_eq!(key(b"\x1b[B"), Key::Down);
+        assert_eq!(key(b"\x1b[5~"), Key::PageUp);
+        assert_eq!(key(b"\x1b[6~"), Key::PageDown);
+        assert_eq!(key(b"\x1b[6"), Key::Other);
+        assert_eq!(key(b"\x1b"), Key::Escape);
+        assert_eq!(key(b"\r"), Key::Enter);
+        assert_eq!(key(b"j"), Key::Down);
+        assert_eq!(key(b"r"), Key::Char('r'));
+        assert_eq!(key(&[3]), Key::Char('q'));
+        assert_eq!(key(b""), Key::Char('q'));
+    }
+
+    #[test]
+    fn parses_cursor_reports() {
+        assert_eq!(parse_cursor_report(b"\x1b[40;120R"), Some((120, 40)));
+        assert_eq!(parse_cursor_report(b"\x1b[40;120"), None);
+        assert_eq!(parse_cursor_report(b""), None);
+    }
+
+    #[test]
+    fn truncates_visible_characters() {
+        assert_eq!(truncate("Part 1: 42", 6), "Part 1");
+        assert_eq!(
+            truncate("\x1b[1m42\x1b[0m (1.0ms)", 4),
+            "\x1b[1m42\x1b[0m ("
+        );
+        assert_eq!(truncate("a\tb", 10), "a b");
+    }
+}
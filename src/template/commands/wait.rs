@@ -0,0 +1,66 @@
+/// Waits for a puzzle to unlock, then scaffolds the day and downloads its input.
+use std::io::{stdout, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use crate::template::calendar::{format_duration, now, unlock_timestamp};
+use crate::template::commands::{all::get_path_for_bin, download, scaffold};
+use crate::template::{aoc_cli, ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+const MAX_ATTEMPTS: u32 = 6;
+
+pub fn handle(day: Day) {
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!("No year configured. Set `year` in aoc.toml or AOC_YEAR.");
+        process::exit(1);
+    };
+
+    // fail before waiting, not at unlock.
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let unlock = unlock_timestamp(year, day);
+
+    loop {
+        let remaining = unlock - now();
+        if remaining <= 0 {
+            break;
+        }
+
+        print!(
+            "\r🎄 Day {day} of {year} unlocks in {ANSI_BOLD}{}{ANSI_RESET}   ",
+            format_duration(remaining)
+        );
+        let _ = stdout().flush();
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    println!("\r🎄 Day {day} of {year} is unlocked!                        ");
+
+    if Path::new(&get_path_for_bin(day)).exists() {
+        println!("Module for day {day} exists already, skipping scaffold.");
+    } else {
+        scaffold::handle(day);
+    }
+
+    // the server can lag a few seconds behind the unlock, so retry with growing delays.
+    for attempt in 1..=MAX_ATTEMPTS {
+        match download::try_handle(day) {
+            Ok(()) => return,
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                let delay = Duration::from_secs(5 << (attempt - 1));
+                eprintln!("Download failed: {e} Retrying in {delay:?}...");
+                thread::sleep(delay);
+            }
+            Err(e) => {
+                eprintln!("Download failed after {MAX_ATTEMPTS} attempts: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,497 @@
+/// Project configuration, loaded from `aoc.toml` (or the file `AOC_CONFIG` points to).
+/// Relative paths in the file are resolved against the project [`root`], not the working directory.
+///
+/// Only the subset of TOML the file needs is supported: `[tables]` and `key = value` pairs
+/// of strings, integers and booleans. Environment variables and command-line flags take
+/// precedence over the file, the file takes precedence over the built-in defaults.
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fs, io, process};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+#[derive(Debug)]
+pub enum Error {
+    Syntax { line: usize, message: String },
+    InvalidValue { key: String, expected: &'static str },
+    UnknownKey(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            Error::InvalidValue { key, expected } => {
+                write!(f, "invalid value for \"{key}\", expected {expected}")
+            }
+            Error::UnknownKey(key) => write!(f, "unknown key \"{key}\""),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A value of the TOML subset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+/// Parameters of `solve --time` benchmarks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bench {
+    /// Time a part is benched for, based on the duration of its first run.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+/// Defaults of command-line flags of `solve` and `all`, `release` also applies to `tui` and `stress`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Defaults {
+    pub release: bool,
+    pub time: bool,
+    pub threads: Option<usize>,
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub year: Option<u16>,
+    /// Directory containing the `inputs`, `examples` and `puzzles` directories.
+    pub data_dir: PathBuf,
+    pub inputs_dir: Option<PathBuf>,
+    pub examples_dir: Option<PathBuf>,
+    pub puzzles_dir: Option<PathBuf>,
+    /// Readme that `all --release --time` writes the benchmark table to.
+    pub readme: PathBuf,
+    /// File containing the session token, passed on to aoc-cli.
+    pub session_file: Option<PathBuf>,
+    pub bench: Bench,
+    pub defaults: Defaults,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            data_dir: PathBuf::from("data"),
+            inputs_dir: None,
+            examples_dir: None,
+            puzzles_dir: None,
+            readme: PathBuf::from("README.md"),
+            session_file: None,
+            bench: Bench {
+                budget: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10_000,
+            },
+            defaults: Defaults::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Directory of a data folder, e.g. `inputs`.
+    #[must_use]
+    pub fn data_dir(&self, folder: &str) -> PathBuf {
+        let configured = match folder {
+            "inputs" => self.inputs_dir.as_ref(),
+            "examples" => self.examples_dir.as_ref(),
+            "puzzles" => self.puzzles_dir.as_ref(),
+            _ => None,
+        };
+
+        configured
+            .cloned()
+            .unwrap_or_else(|| self.data_dir.join(folder))
+    }
+
+    /// Builds the configuration from parsed `key = value` pairs, with keys of tables prefixed by the table name.
+    pub fn from_entries(entries: &[(String, Value)]) -> Result<Self, Error> {
+        let mut config = Self::default();
+
+        for (key, value) in entries {
+            let invalid = |expected| Error::InvalidValue {
+                key: key.clone(),
+                expected,
+            };
+            let string = || match value {
+                Value::String(s) => Ok(s.clone()),
+                _ => Err(invalid("a string")),
+            };
+            let path = || string().map(|s| expand_home(&s));
+            let number = || match value {
+                Value::Integer(n) => u64::try_from(*n).map_err(|_| invalid("a positive integer")),
+                _ => Err(invalid("a positive integer")),
+            };
+            let samples = || match number()? {
+                0 => Err(invalid("at least 1 sample")),
+                n => Ok(u128::from(n)),
+            };
+            let boolean = || match value {
+                Value::Boolean(b) => Ok(*b),
+                _ => Err(invalid("true or false")),
+            };
+
+            match key.as_str() {
+                "year" => {
+                    config.year = Some(number()?.try_into().map_err(|_| invalid("a year"))?);
+                }
+                "paths.data" => config.data_dir = path()?,
+                "paths.inputs" => config.inputs_dir = Some(path()?),
+                "paths.examples" => config.examples_dir = Some(path()?),
+                "paths.puzzles" => config.puzzles_dir = Some(path()?),
+                "paths.readme" => config.readme = path()?,
+                "paths.session" => config.session_file = Some(path()?),
+                "bench.budget_ms" => config.bench.budget = Duration::from_millis(number()?),
+                "bench.min_samples" => config.bench.min_samples = samples()?,
+                "bench.max_samples" => config.bench.max_samples = samples()?,
+                "defaults.release" => config.defaults.release = boolean()?,
+                "defaults.time" => config.defaults.time = boolean()?,
+                "defaults.threads" => {
+                    let threads = number()?;
+                    if threads == 0 {
+                        return Err(invalid("at least 1 thread"));
+                    }
+                    config.defaults.threads =
+                        Some(threads.try_into().map_err(|_| invalid("a thread count"))?);
+                }
+                "defaults.timeout" => {
+                    config.defaults.timeout = Some(Duration::from_secs(number()?));
+                }
+                _ => return Err(Error::UnknownKey(key.clone())),
+            }
+        }
+
+        if config.bench.min_samples > config.bench.max_samples {
+            return Err(Error::InvalidValue {
+                key: "bench.min_samples".into(),
+                expected: "at most bench.max_samples",
+            });
+        }
+
+        Ok(config)
+    }
+
+    /// Loads the configuration from a file, falling back to the defaults if it does not exist.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_entries(&parse(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Resolves relative paths against `root`.
+    fn resolve(&mut self, root: &Path) {
+        for path in [&mut self.data_dir, &mut self.readme] {
+            *path = root.join(&*path);
+        }
+
+        for path in [
+            &mut self.inputs_dir,
+            &mut self.examples_dir,
+            &mut self.puzzles_dir,
+            &mut self.session_file,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *path = root.join(&*path);
+        }
+    }
+
+    /// Applies overrides from environment variables.
+    fn apply_env(&mut self) {
+        if let Some(year) = env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok()) {
+            self.year = Some(year);
+        }
+    }
+}
+
+/// The configuration of the project, loaded on first use.
+///
+/// Exits if the configuration file is invalid.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| {
+        let path = env::var_os(CONFIG_ENV).map_or_else(|| root().join(CONFIG_FILE), PathBuf::from);
+
+        let mut config = Config::load(&path).unwrap_or_else(|e| {
+            eprintln!("Invalid configuration in {path:?}: {e}");
+            process::exit(1);
+        });
+
+        config.resolve(root());
+        config.apply_env();
+        config
+    })
+}
+
+/// Root directory of the project, so files are found regardless of the working directory
+/// (e.g. when tests are run from a subdirectory or an IDE).
+///
+/// This is the crate root the template was compiled in, or the working directory if the
+/// binary was moved to a machine where it does not exist.
+pub fn root() -> &'static Path {
+    static ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+    ROOT.get_or_init(|| {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        if manifest_dir.is_dir() {
+            manifest_dir.to_path_buf()
+        } else {
+            env::current_dir().unwrap_or_default()
+        }
+    })
+}
+
+/// Cargo's target directory: `CARGO_TARGET_DIR` if set, `target` in the project [`root`] otherwise.
+#[must_use]
+pub fn target_dir() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR").map_or_else(|| root().join("target"), PathBuf::from)
+}
+
+/// Shortens a path inside the project to be relative to its root, for messages.
+#[must_use]
+pub fn display_path(path: &Path) -> std::path::Display<'_> {
+    path.strip_prefix(root()).unwrap_or(path).display()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => env::var("HOME")
+            .or_else(|_| env::var("USERPROFILE"))
+            .map_or_else(|_| PathBuf::from(path), |home| Path::new(&home).join(rest)),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Parses a configuration into `key = value` pairs.
+/// Keys in a `[table]` are prefixed with its name, e.g. `bench.budget_ms`.
+pub fn parse(input: &str) -> Result<Vec<(String, Value)>, Error> {
+    let mut table = String::new();
+    let mut entries: Vec<(String, Value)> = vec![];
+
+    for (i, line) in input.lines().enumerate() {
+        let syntax = |message: &str| Error::Syntax {
+            line: i + 1,
+            message: message.into(),
+        };
+
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| syntax("expected ']'"))?;
+            if !is_bare_key(name.trim()) {
+                return Err(syntax("invalid table name"));
+            }
+            table = name.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| syntax("expected `key = value`"))?;
+
+        let key = key.trim();
+        if !is_bare_key(key) {
+            return Err(syntax("invalid key"));
+        }
+
+        let key = if table.is_empty() {
+            key.to_string()
+        } else {
+            format!("{table}.{key}")
+        };
+
+        if entries.iter().any(|(k, _)| *k == key) {
+            return Err(syntax(&format!("duplicate key \"{key}\"")));
+        }
+
+        let value = parse_value(value.trim()).ok_or_else(|| syntax("invalid value"))?;
+        entries.push((key, value));
+    }
+
+    Ok(entries)
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Removes a trailing `# comment`, unless the `#` is part of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    line
+}
+
+fn parse_value(value: &str) -> Option<Value> {
+    if let Some(literal) = value.strip_prefix('\'') {
+        let literal = literal.strip_suffix('\'')?;
+        return (!literal.contains('\'')).then(|| Value::String(literal.into()));
+    }
+
+    if let Some(basic) = value.strip_prefix('"') {
+        let basic = basic.strip_suffix('"')?;
+        let mut out = String::new();
+        let mut chars = basic.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => out.push(match chars.next()? {
+                    '"' => '"',
+                    '\\' => '\\',
+                    'n' => '\n',
+                    't' => '\t',
+                    _ => return None,
+                }),
+                '"' => return None,
+                c => out.push(c),
+            }
+        }
+
+        return Some(Value::String(out));
+    }
+
+    match value {
+        "true" => Some(Value::Boolean(true)),
+        "false" => Some(Value::Boolean(false)),
+        _ => value.replace('_', "").parse().ok().map(Value::Integer),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Config, Error, Value};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_tables_and_values() {
+        let entries = parse(
+            r#"
+            # comment
+            year = 2023
+
+            [paths]
+            data = "my data # not a comment" # comment
+            readme = 'C:\docs\README.md'
+
+            [bench]
+            max_samples = 1_000
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                ("year".into(), Value::Integer(2023)),
+                (
+                    "paths.data".into(),
+                    Value::String("my data # not a comment".into())
+                ),
+                (
+                    "paths.readme".into(),
+                    Value::String(r"C:\docs\README.md".into())
+                ),
+                ("bench.max_samples".into(), Value::Integer(1000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_syntax() {
+        assert!(matches!(parse("year"), Err(Error::Syntax { line: 1, .. })));
+        assert!(matches!(
+            parse("\n[paths"),
+            Err(Error::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse("a = 1\na = 2"),
+            Err(Error::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(parse("a = \"b"), Err(Error::Syntax { .. })));
+    }
+
+    #[test]
+    fn builds_config() {
+        let entries = parse(
+            r#"
+            year = 2022
+            [paths]
+            data = "puzzles"
+            examples = "tests/examples"
+            [defaults]
+            release = true
+            threads = 4
+            timeout = 30
+            "#,
+        )
+        .unwrap();
+        let config = Config::from_entries(&entries).unwrap();
+
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.data_dir("inputs"), PathBuf::from("puzzles/inputs"));
+        assert_eq!(config.data_dir("examples"), PathBuf::from("tests/examples"));
+        assert!(config.defaults.release);
+        assert_eq!(config.defaults.threads, Some(4));
+        assert_eq!(config.defaults.timeout, Some(Duration::from_secs(30)));
+
+        let entries = parse("[paths]\nreadme = 1").unwrap();
+        assert!(matches!(
+            Config::from_entries(&entries),
+            Err(Error::InvalidValue { .. })
+        ));
+        let entries = parse("[bench]\nmin_samples = 0").unwrap();
+        assert!(matches!(
+            Config::from_entries(&entries),
+            Err(Error::InvalidValue { .. })
+        ));
+        let entries = parse("[bench]\nsamples = 1").unwrap();
+        assert!(matches!(
+            Config::from_entries(&entries),
+            Err(Error::UnknownKey(_))
+        ));
+    }
+}
@@ -0,0 +1,448 @@
+/// Module that stores inputs and puzzles encrypted, so they can be committed without publishing them.
+///
+/// Encryption is enabled when a key is configured, either with the `AOC_INPUT_KEY` environment variable
+/// or in a `.aoc-key` file. The key has to be 32 random bytes in hex, e.g. from `openssl rand -hex 32`.
+/// It is used without stretching, so passphrases are rejected as they could be brute-forced. Encrypted files sit next to their plaintext with an added `.enc` extension.
+/// They are encrypted with ChaCha20 and authenticated with HMAC-SHA256, both implemented below.
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::template::config;
+
+const MAGIC: &[u8; 8] = b"aoc-enc1";
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 32;
+
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+pub const KEY_FILE: &str = ".aoc-key";
+
+#[derive(Debug)]
+pub enum Error {
+    MissingKey,
+    /// The configured key is not 32 bytes in hex.
+    InvalidKey,
+    InvalidFormat,
+    /// The file was modified or encrypted with another key.
+    Authentication,
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingKey => write!(
+                f,
+                "no key configured, set {KEY_ENV} or create a {KEY_FILE} file"
+            ),
+            Error::InvalidKey => write!(
+                f,
+                "key must be 64 hex characters of random data, e.g. from `openssl rand -hex 32`"
+            ),
+            Error::InvalidFormat => write!(f, "file is not an encrypted input"),
+            Error::Authentication => write!(f, "file could not be authenticated with this key"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Keys derived from the configured secret.
+pub struct Key {
+    cipher: [u8; 32],
+    mac: [u8; 32],
+}
+
+impl Key {
+    /// Derives the keys from a secret of 32 random bytes in hex.
+    pub fn from_secret(secret: &str) -> Result<Self, Error> {
+        let secret = secret.trim();
+        if secret.len() != 64 || !secret.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::InvalidKey);
+        }
+
+        let master: Vec<u8> = (0..secret.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&secret[i..i + 2], 16).map_err(|_| Error::InvalidKey))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            cipher: hmac_sha256(&master, b"encrypt"),
+            mac: hmac_sha256(&master, b"authenticate"),
+        })
+    }
+
+    /// Loads the key from `AOC_INPUT_KEY` or the `.aoc-key` file in the project root.
+    pub fn load() -> Result<Self, Error> {
+        Self::from_secret(&secret().ok_or(Error::MissingKey)?)
+    }
+}
+
+fn secret() -> Option<String> {
+    env::var(KEY_ENV)
+        .ok()
+        .or_else(|| fs::read_to_string(config::root().join(KEY_FILE)).ok())
+        .filter(|secret| !secret.trim().is_empty())
+}
+
+/// Returns `true` if a key is configured and inputs should be stored encrypted.
+#[must_use]
+pub fn is_enabled() -> bool {
+    secret().is_some()
+}
+
+/// Path of the encrypted counterpart of a file, e.g. `data/inputs/01.txt.enc`.
+#[must_use]
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".enc");
+    PathBuf::from(path)
+}
+
+/// Reads a file, falling back to decrypting its `.enc` counterpart if only that exists.
+pub fn read_to_string(path: &Path) -> Result<String, Error> {
+    let encrypted = encrypted_path(path);
+
+    if path.exists() || !encrypted.exists() {
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    let key = Key::load()?;
+    let plaintext = decrypt(&key, &fs::read(encrypted)?)?;
+    String::from_utf8(plaintext).map_err(|_| Error::InvalidFormat)
+}
+
+/// Writes the encrypted counterpart of a file and returns its path.
+pub fn encrypt_file(path: &Path) -> Result<PathBuf, Error> {
+    let key = Key::load()?;
+    let encrypted = encrypted_path(path);
+    fs::write(&encrypted, encrypt(&key, &fs::read(path)?))?;
+    Ok(encrypted)
+}
+
+/// Encrypts `plaintext` as `magic || nonce || ciphertext || tag`.
+///
+/// The nonce is derived from the plaintext, so encrypting the same file twice yields the same bytes
+/// and re-running `download` does not show up as a change in git.
+#[must_use]
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(&hmac_sha256(&key.mac, &[b"nonce", plaintext].concat())[..NONCE_LEN]);
+
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&nonce);
+    out.extend(chacha20(&key.cipher, &nonce, plaintext));
+
+    let tag = hmac_sha256(&key.mac, &out);
+    out.extend_from_slice(&tag);
+    out
+}
+
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<Vec<u8>, Error> {
+    if data.len() < MAGIC.len() + NONCE_LEN + TAG_LEN || !data.starts_with(MAGIC) {
+        return Err(Error::InvalidFormat);
+    }
+
+    let (message, tag) = data.split_at(data.len() - TAG_LEN);
+    let expected = hmac_sha256(&key.mac, message);
+
+    // compare in constant time.
+    if tag
+        .iter()
+        .zip(expected)
+        .fold(0, |acc, (a, b)| acc | (a ^ b))
+        != 0
+    {
+        return Err(Error::Authentication);
+    }
+
+    let (nonce, ciphertext) = message[MAGIC.len()..].split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().map_err(|_| Error::InvalidFormat)?;
+
+    Ok(chacha20(&key.cipher, &nonce, ciphertext))
+}
+
+/// ChaCha20 as specified in RFC 8439, with the block counter starting at 1.
+fn chacha20(key: &[u8; 32], nonce: &[u8; NONCE_LEN], data: &[u8]) -> Vec<u8> {
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&[0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574]);
+    for (i, chunk) in key.chunks(4).enumerate() {
+        state[4 + i] = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    for (i, chunk) in nonce.chunks(4).enumerate() {
+        state[13 + i] = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+
+    let mut out = Vec::with_capacity(data.len());
+
+    for (counter, block) in (1u32..).zip(data.chunks(64)) {
+        state[12] = counter;
+        let stream = chacha20_block(&state);
+        out.extend(block.iter().zip(stream).map(|(a, b)| a ^ b));
+    }
+
+    out
+}
+
+fn chacha20_block(state: &[u32; 16]) -> [u8; 64] {
+    fn quarter_round(s: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+        s[a] = s[a].wrapping_add(s[b]);
+        s[d] = (s[d] ^ s[a]).rotate_left(16);
+        s[c] = s[c].wrapping_add(s[d]);
+        s[b] = (s[b] ^ s[c]).rotate_left(12);
+        s[a] = s[a].wrapping_add(s[b]);
+        s[d] = (s[d] ^ s[a]).rotate_left(8);
+        s[c] = s[c].wrapping_add(s[d]);
+        s[b] = (s[b] ^ s[c]).rotate_left(7);
+    }
+
+    let mut working = *state;
+    for _ in 0..10 {
+        quarter_round(&mut working, 0, 4, 8, 12);
+        quarter_round(&mut working, 1, 5, 9, 13);
+        quarter_round(&mut working, 2, 6, 10, 14);
+        quarter_round(&mut working, 3, 7, 11, 15);
+        quarter_round(&mut working, 0, 5, 10, 15);
+        quarter_round(&mut working, 1, 6, 11, 12);
+        quarter_round(&mut working, 2, 7, 8, 13);
+        quarter_round(&mut working, 3, 4, 9, 14);
+    }
+
+    let mut out = [0u8; 64];
+    for (i, word) in working.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&word.wrapping_add(state[i]).to_le_bytes());
+    }
+    out
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; 64];
+    if key.len() > 64 {
+        block[..32].copy_from_slice(&sha256(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let inner: Vec<u8> = block
+        .iter()
+        .map(|b| b ^ 0x36)
+        .chain(message.iter().copied())
+        .collect();
+    let outer: Vec<u8> = block
+        .iter()
+        .map(|b| b ^ 0x5c)
+        .chain(sha256(&inner))
+        .collect();
+
+    sha256(&outer)
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    const K: [u32; 64] = [
+        0x428a_2f98,
+        0x7137_4491,
+        0xb5c0_fbcf,
+        0xe9b5_dba5,
+        0x3956_c25b,
+        0x59f1_11f1,
+        0x923f_82a4,
+        0xab1c_5ed5,
+        0xd807_aa98,
+        0x1283_5b01,
+        0x2431_85be,
+        0x550c_7dc3,
+        0x72be_5d74,
+        0x80de_b1fe,
+        0x9bdc_06a7,
+        0xc19b_f174,
+        0xe49b_69c1,
+        0xefbe_4786,
+        0x0fc1_9dc6,
+        0x240c_a1cc,
+        0x2de9_2c6f,
+        0x4a74_84aa,
+        0x5cb0_a9dc,
+        0x76f9_88da,
+        0x983e_5152,
+        0xa831_c66d,
+        0xb003_27c8,
+        0xbf59_7fc7,
+        0xc6e0_0bf3,
+        0xd5a7_9147,
+        0x06ca_6351,
+        0x1429_2967,
+        0x27b7_0a85,
+        0x2e1b_2138,
+        0x4d2c_6dfc,
+        0x5338_0d13,
+        0x650a_7354,
+        0x766a_0abb,
+        0x81c2_c92e,
+        0x9272_2c85,
+        0xa2bf_e8a1,
+        0xa81a_664b,
+        0xc24b_8b70,
+        0xc76c_51a3,
+        0xd192_e819,
+        0xd699_0624,
+        0xf40e_3585,
+        0x106a_a070,
+        0x19a4_c116,
+        0x1e37_6c08,
+        0x2748_774c,
+        0x34b0_bcb5,
+        0x391c_0cb3,
+        0x4ed8_aa4a,
+        0x5b9c_ca4f,
+        0x682e_6ff3,
+        0x748f_82ee,
+        0x78a5_636f,
+        0x84c8_7814,
+        0x8cc7_0208,
+        0x90be_fffa,
+        0xa450_6ceb,
+        0xbef9_a3f7,
+        0xc671_78f2,
+    ];
+
+    let mut h: [u32; 8] = [
+        0x6a09_e667,
+        0xbb67_ae85,
+        0x3c6e_f372,
+        0xa54f_f53a,
+        0x510e_527f,
+        0x9b05_688c,
+        0x1f83_d9ab,
+        0x5be0_cd19,
+    ];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend(((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+
+    let mut out = [0u8; 32];
+    for (i, word) in h.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{chacha20, decrypt, encrypt, hmac_sha256, sha256, Error, Key};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn hashes() {
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn encrypts_rfc_8439_vector() {
+        let key: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let nonce = [0, 0, 0, 0, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+        let ciphertext = chacha20(&key, &nonce, plaintext);
+        assert_eq!(hex(&ciphertext[..16]), "6e2e359a2568f98041ba0728dd0d6981");
+        assert_eq!(chacha20(&key, &nonce, &ciphertext), plaintext);
+    }
+
+    #[test]
+    fn round_trips_and_authenticates() {
+        let key = Key::from_secret(&"0f".repeat(32)).unwrap();
+        let encrypted = encrypt(&key, b"1abc2\npqr3stu8vwx\n");
+
+        assert_eq!(encrypted, encrypt(&key, b"1abc2\npqr3stu8vwx\n"));
+        assert_eq!(decrypt(&key, &encrypted).unwrap(), b"1abc2\npqr3stu8vwx\n");
+
+        let other = Key::from_secret(&"f0".repeat(32)).unwrap();
+        assert!(matches!(
+            decrypt(&other, &encrypted),
+            Err(Error::Authentication)
+        ));
+
+        let mut tampered = encrypted.clone();
+        tampered[20] ^= 1;
+        assert!(matches!(
+            decrypt(&key, &tampered),
+            Err(Error::Authentication)
+        ));
+        assert!(matches!(decrypt(&key, b"1abc2"), Err(Error::InvalidFormat)));
+    }
+
+    #[test]
+    fn rejects_passphrases() {
+        assert!(matches!(
+            Key::from_secret("hunter2"),
+            Err(Error::InvalidKey)
+        ));
+        assert!(matches!(
+            Key::from_secret(&"g0".repeat(32)),
+            Err(Error::InvalidKey)
+        ));
+        assert!(Key::from_secret(&format!("{}\n", "AB".repeat(32))).is_ok());
+    }
+}
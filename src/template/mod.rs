@@ -1,48 +1,128 @@
 use crate::Day;
-use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod baseline;
+pub mod calendar;
 pub mod commands;
+pub mod config;
+pub mod crypt;
+pub mod json;
 pub mod readme_benchmarks;
+pub mod report;
 pub mod runner;
+pub mod stress;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Files that are only stored encrypted are decrypted transparently, see [`crypt`].
+/// Folders are resolved against the data directories of the [`config`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let filepath = config::get().data_dir(folder).join(format!("{day}.txt"));
+    let f = crypt::read_to_string(&filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
+    let filepath = config::get()
+        .data_dir(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = crypt::read_to_string(&filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Additional implementations of a part can be registered after the day, e.g.
+/// `solution!(5, part_two: [part_two_fast])`. They are run and cross-checked by `solve <day> --compare`.
+///
+/// A generator for scaled-up inputs can be registered with `solution!(5, stress: scale_input)`.
+/// It is used by `stress <day> --scale <k>`.
+///
+/// A visualization can be registered with `solution!(3, visualize: visualize_engine)`, where
+/// `fn visualize_engine(input: &str)`. It runs once after the parts with `solve <day> --visualize`,
+/// so it is never part of the timings.
+///
+/// With the `embed_input` feature, the input of the day is embedded into the binary at compile time.
+/// `build.rs` resolves it from the configured inputs directory and decrypts `.enc` inputs.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($day:expr $(, part_one: [$($one:ident),* $(,)?])? $(, part_two: [$($two:ident),* $(,)?])? $(, stress: $stress:path)? $(, visualize: $visualize:path)? $(,)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            configure_threads();
+            #[cfg(not(feature = "embed_input"))]
+            let input = read_input(DAY);
+            // the binary is named after the zero-padded day, like its input.
+            #[cfg(feature = "embed_input")]
+            let input = read_embedded_input(
+                DAY,
+                include_str!(concat!(env!("OUT_DIR"), "/inputs/", env!("CARGO_BIN_NAME"), ".txt")),
+            );
+
+            if let Some(scale) = get_stress_scale() {
+                let generate = None $(.or(Some($stress as advent_of_code::template::stress::Generator)))?;
+                if is_part_selected(1) {
+                    advent_of_code::template::stress::run(part_one, generate, &input, scale, 1);
+                }
+                if is_part_selected(2) {
+                    advent_of_code::template::stress::run(part_two, generate, &input, scale, 2);
+                }
+                return;
+            }
+
+            if is_speedup_run() {
+                if is_part_selected(1) {
+                    run_speedup(part_one, &input, 1);
+                }
+                if is_part_selected(2) {
+                    run_speedup(part_two, &input, 2);
+                }
+                return;
+            }
+
+            if is_compare_run() {
+                let one_ok = !is_part_selected(1) || compare_part(
+                    &[
+                        ("part_one", &part_one as &dyn Fn(&str) -> _),
+                        $($((stringify!($one), &$one as &dyn Fn(&str) -> _),)*)?
+                    ],
+                    &input,
+                    1,
+                );
+                let two_ok = !is_part_selected(2) || compare_part(
+                    &[
+                        ("part_two", &part_two as &dyn Fn(&str) -> _),
+                        $($((stringify!($two), &$two as &dyn Fn(&str) -> _),)*)?
+                    ],
+                    &input,
+                    2,
+                );
+                if !(one_ok && two_ok) {
+                    std::process::exit(1);
+                }
+                return;
+            }
+
+            let one = is_part_selected(1).then(|| run_part(part_one, &input, DAY, 1));
+            let two = is_part_selected(2).then(|| run_part(part_two, &input, DAY, 2));
+
+            if advent_of_code::visualize::is_enabled() {
+                let visualize = None $(.or(Some($visualize as fn(&str))))?;
+                run_visualization(visualize, &input);
+            }
+
+            if [one, two].iter().flatten().any(Answer::is_failed) {
+                std::process::exit(1);
+            }
         }
     };
 }
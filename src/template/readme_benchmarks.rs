@@ -2,6 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::config;
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -23,7 +24,10 @@ pub struct Timings {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
     pub total_nanos: f64,
+    pub threads: Option<usize>,
 }
 
 pub struct TablePosition {
@@ -69,6 +73,8 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
         "| :---: | :---: | :---:  |".into(),
     ];
 
+    let threads = timings.iter().find_map(|t| t.threads);
+
     for timing in timings {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
@@ -82,6 +88,11 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if let Some(threads) = threads {
+        lines.push(format!("_Measured with {threads} threads._"));
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
@@ -95,7 +106,7 @@ fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> R
 }
 
 pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
+    let path = &config::get().readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     update_content(&mut readme, timings, total_millis)?;
     fs::write(path, &readme)?;
@@ -113,19 +124,28 @@ mod tests {
                 day: day!(1),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                part_1_nanos: Some(1e+7),
+                part_2_nanos: Some(2e+7),
                 total_nanos: 3e+10,
+                threads: None,
             },
             Timings {
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                part_1_nanos: Some(3e+7),
+                part_2_nanos: Some(4e+7),
                 total_nanos: 7e+10,
+                threads: None,
             },
             Timings {
                 day: day!(4),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                part_1_nanos: Some(4e+7),
+                part_2_nanos: Some(5e+7),
                 total_nanos: 9e+10,
+                threads: None,
             },
         ]
     }
@@ -0,0 +1,370 @@
+/// Module that exports benchmark timings as CSV, JSON and a self-contained HTML report.
+use std::fmt::Write as _;
+use std::path::Path;
+use std::{fs, io};
+
+use crate::template::readme_benchmarks::Timings;
+use crate::Day;
+
+const CHART_WIDTH: f64 = 720.0;
+const CHART_LABEL_WIDTH: f64 = 110.0;
+const CHART_BAR_HEIGHT: f64 = 16.0;
+const CHART_BAR_GAP: f64 = 4.0;
+const CHART_GROUP_GAP: f64 = 10.0;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Writes `benchmarks.csv`, `benchmarks.json` and `benchmarks.html` into the `dir` folder.
+pub fn write(dir: &Path, timings: &[Timings], total_millis: f64) -> Result<(), Error> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join("benchmarks.csv"), to_csv(timings))?;
+    fs::write(dir.join("benchmarks.json"), to_json(timings, total_millis))?;
+    fs::write(dir.join("benchmarks.html"), to_html(timings, total_millis))?;
+    Ok(())
+}
+
+fn parts(timing: &Timings) -> [(u8, Option<&str>, Option<f64>); 2] {
+    [
+        (1, timing.part_1.as_deref(), timing.part_1_nanos),
+        (2, timing.part_2.as_deref(), timing.part_2_nanos),
+    ]
+}
+
+/// Serializes timings to CSV with one row per solved part.
+#[must_use]
+pub fn to_csv(timings: &[Timings]) -> String {
+    let mut lines = vec!["day,part,time,nanos,threads".to_string()];
+
+    for timing in timings {
+        let threads = timing.threads.map(|t| t.to_string()).unwrap_or_default();
+
+        for (part, time, nanos) in parts(timing) {
+            if let (Some(time), Some(nanos)) = (time, nanos) {
+                lines.push(format!(
+                    "{},{part},{time},{nanos},{threads}",
+                    timing.day.into_inner()
+                ));
+            }
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Parses timings that were serialized with [`to_csv`].
+pub fn from_csv(csv: &str) -> Result<Vec<Timings>, Error> {
+    let mut timings: Vec<Timings> = vec![];
+
+    for line in csv.lines().skip(1).filter(|l| !l.is_empty()) {
+        let fields: Vec<&str> = line.split(',').collect();
+
+        // baselines saved before thread counts were recorded have no `threads` column.
+        let (day, part, time, nanos, threads) = match fields[..] {
+            [day, part, time, nanos] => (day, part, time, nanos, ""),
+            [day, part, time, nanos, threads] => (day, part, time, nanos, threads),
+            _ => return Err(Error::Parser(format!("malformed line: {line}"))),
+        };
+
+        let day: Day = day
+            .parse()
+            .map_err(|_| Error::Parser(format!("invalid day in line: {line}")))?;
+        let nanos: f64 = nanos
+            .parse()
+            .map_err(|_| Error::Parser(format!("invalid nanos in line: {line}")))?;
+        let threads: Option<usize> = match threads {
+            "" => None,
+            t => Some(
+                t.parse()
+                    .map_err(|_| Error::Parser(format!("invalid threads in line: {line}")))?,
+            ),
+        };
+
+        let index = match timings.iter().position(|t| t.day == day) {
+            Some(index) => index,
+            None => {
+                timings.push(Timings {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                    part_1_nanos: None,
+                    part_2_nanos: None,
+                    total_nanos: 0_f64,
+                    threads,
+                });
+                timings.len() - 1
+            }
+        };
+
+        let timing = &mut timings[index];
+
+        match part {
+            "1" => {
+                timing.part_1 = Some(time.into());
+                timing.part_1_nanos = Some(nanos);
+            }
+            "2" => {
+                timing.part_2 = Some(time.into());
+                timing.part_2_nanos = Some(nanos);
+            }
+            _ => return Err(Error::Parser(format!("invalid part in line: {line}"))),
+        }
+
+        timing.total_nanos += nanos;
+    }
+
+    Ok(timings)
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_part(time: Option<&str>, nanos: Option<f64>) -> String {
+    match (time, nanos) {
+        (Some(time), Some(nanos)) => {
+            format!("{{\"time\": {}, \"nanos\": {nanos}}}", json_string(time))
+        }
+        _ => "null".into(),
+    }
+}
+
+fn to_json(timings: &[Timings], total_millis: f64) -> String {
+    let days: Vec<String> = timings
+        .iter()
+        .map(|timing| {
+            let [(_, p1, n1), (_, p2, n2)] = parts(timing);
+            format!(
+                "    {{\"day\": {}, \"part_1\": {}, \"part_2\": {}, \"total_nanos\": {}, \"threads\": {}}}",
+                timing.day.into_inner(),
+                json_part(p1, n1),
+                json_part(p2, n2),
+                timing.total_nanos,
+                timing.threads.map_or("null".into(), |t| t.to_string())
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\n  \"total_millis\": {total_millis},\n  \"days\": [\n{}\n  ]\n}}\n",
+        days.join(",\n")
+    )
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders a horizontal bar chart. Timings span several orders of magnitude, so bars use a log scale.
+fn to_svg(timings: &[Timings]) -> String {
+    let bars: Vec<(String, u8, &str, f64)> = timings
+        .iter()
+        .flat_map(|timing| {
+            parts(timing).into_iter().filter_map(|(part, time, nanos)| {
+                Some((
+                    format!("Day {}", timing.day.into_inner()),
+                    part,
+                    time?,
+                    nanos?,
+                ))
+            })
+        })
+        .collect();
+
+    let groups = timings.len() as f64;
+    let height = bars.len() as f64 * (CHART_BAR_HEIGHT + CHART_BAR_GAP) + groups * CHART_GROUP_GAP;
+
+    let log = |nanos: f64| nanos.max(1.0).log10();
+    let max_log = bars.iter().map(|b| log(b.3)).fold(0.0, f64::max) + 1.0;
+    let bar_space = CHART_WIDTH - CHART_LABEL_WIDTH - 90.0;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{CHART_WIDTH}\" height=\"{height}\" font-family=\"monospace\" font-size=\"12\">\n"
+    );
+
+    let mut y = 0.0;
+    let mut last_label = None;
+
+    for (label, part, time, nanos) in &bars {
+        if last_label != Some(label) {
+            if last_label.is_some() {
+                y += CHART_GROUP_GAP;
+            }
+            let _ = writeln!(
+                svg,
+                "  <text x=\"0\" y=\"{}\">{}</text>",
+                y + CHART_BAR_HEIGHT - 4.0,
+                html_escape(label)
+            );
+            last_label = Some(label);
+        }
+
+        let width = (log(*nanos) / max_log * bar_space).max(1.0);
+        let color = if *part == 1 { "#4e79a7" } else { "#f28e2b" };
+
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{CHART_LABEL_WIDTH}\" y=\"{y}\" width=\"{width:.1}\" height=\"{CHART_BAR_HEIGHT}\" fill=\"{color}\"><title>Part {part}: {}</title></rect>",
+            html_escape(time)
+        );
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{:.1}\" y=\"{}\">P{part} {}</text>",
+            CHART_LABEL_WIDTH + width + 6.0,
+            y + CHART_BAR_HEIGHT - 4.0,
+            html_escape(time)
+        );
+
+        y += CHART_BAR_HEIGHT + CHART_BAR_GAP;
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn to_html(timings: &[Timings], total_millis: f64) -> String {
+    let rows: String = timings
+        .iter()
+        .map(|timing| {
+            format!(
+                "      <tr><td>Day {}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                timing.day.into_inner(),
+                html_escape(timing.part_1.as_deref().unwrap_or("-")),
+                html_escape(timing.part_2.as_deref().unwrap_or("-")),
+                timing.threads.map_or("-".into(), |t| t.to_string())
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <title>Advent of Code Benchmarks</title>
+    <style>
+      body {{ font-family: sans-serif; margin: 2em; }}
+      table {{ border-collapse: collapse; margin-bottom: 2em; }}
+      th, td {{ border: 1px solid #ccc; padding: 4px 12px; text-align: center; }}
+    </style>
+  </head>
+  <body>
+    <h1>Benchmarks</h1>
+    <table>
+      <tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Threads</th></tr>
+{rows}    </table>
+    <p><strong>Total: {total_millis:.2}ms</strong></p>
+    {}
+  </body>
+</html>
+"#,
+        to_svg(timings)
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_csv, to_csv, to_html, to_json, Timings};
+    use crate::day;
+
+    fn get_mock_timings() -> Vec<Timings> {
+        vec![
+            Timings {
+                day: day!(1),
+                part_1: Some("10.0ms".into()),
+                part_2: Some("20.0µs".into()),
+                part_1_nanos: Some(1e+7),
+                part_2_nanos: Some(2e+4),
+                total_nanos: 10_020_000.0,
+                threads: Some(8),
+            },
+            Timings {
+                day: day!(3),
+                part_1: Some("5.0ns".into()),
+                part_2: None,
+                part_1_nanos: Some(5.0),
+                part_2_nanos: None,
+                total_nanos: 5.0,
+                threads: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn csv_has_one_row_per_part() {
+        let csv = to_csv(&get_mock_timings());
+        let expected = [
+            "day,part,time,nanos,threads",
+            "1,1,10.0ms,10000000,8",
+            "1,2,20.0µs,20000,8",
+            "3,1,5.0ns,5,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let timings = from_csv(&to_csv(&get_mock_timings())).unwrap();
+        assert_eq!(timings.len(), 2);
+        assert_eq!(timings[0].part_2.as_deref(), Some("20.0µs"));
+        assert_eq!(timings[0].total_nanos, 10_020_000.0);
+        assert_eq!(timings[0].threads, Some(8));
+        assert_eq!(timings[1].part_1_nanos, Some(5.0));
+        assert_eq!(timings[1].part_2, None);
+    }
+
+    #[test]
+    fn csv_without_threads_column() {
+        let csv = "day,part,time,nanos\n1,1,10.0ms,10000000\n1,2,20.0µs,20000\n";
+        let timings = from_csv(csv).unwrap();
+        assert_eq!(timings.len(), 1);
+        assert_eq!(timings[0].total_nanos, 10_020_000.0);
+        assert_eq!(timings[0].threads, None);
+        assert!(from_csv("day,part,time\n1,1,10.0ms\n").is_err());
+    }
+
+    #[test]
+    fn json_marks_missing_parts_as_null() {
+        let json = to_json(&get_mock_timings(), 10.02);
+        assert!(json.contains("\"total_millis\": 10.02"));
+        assert!(json.contains("{\"day\": 3, \"part_1\": {\"time\": \"5.0ns\", \"nanos\": 5}, \"part_2\": null, \"total_nanos\": 5, \"threads\": null}"));
+    }
+
+    #[test]
+    fn html_is_self_contained() {
+        let html = to_html(&get_mock_timings(), 10.02);
+        assert!(html.contains("<svg"));
+        assert_eq!(html.matches("<rect").count(), 3);
+        assert!(!html.contains("src="));
+        assert!(!html.contains("<link"));
+    }
+}
@@ -1,42 +1,370 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::{aoc_cli, config, crypt, read_file, ANSI_ITALIC, ANSI_RESET};
+use crate::{trace, Day};
+use std::error::Error;
 use std::fmt::Display;
-use std::io::{stdout, Write};
+use std::io::{stdin, stdout, Read, Write};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The outcome of running a solution part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    /// The part returned `None`, i.e. it is not implemented yet.
+    Unsolved,
+    /// The part returned an error, rendered with its chain of sources.
+    Failed(String),
+}
+
+impl Answer {
+    #[must_use]
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Answer::Failed(_))
+    }
+}
+
+/// Return types that solution parts can use: `Option<T>`, `Result<T, E>` and `Result<Option<T>, E>`
+/// for any `T: Display`.
+///
+/// `Kind` tells the impls apart, `Result<T, E>` would overlap with `Result<Option<T>, E>` otherwise.
+/// It is inferred from the return type and never has to be named.
+pub trait PartOutput<Kind> {
+    fn into_answer(self) -> Answer;
+}
+
+/// [`PartOutput`] kind of parts that always return an answer on success.
+pub enum Plain {}
+
+/// [`PartOutput`] kind of parts that return `None` while they are not implemented.
+pub enum Optional {}
+
+impl<T: Display> PartOutput<Optional> for Option<T> {
+    fn into_answer(self) -> Answer {
+        match self {
+            Some(result) => Answer::Solved(result.to_string()),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput<Plain> for Result<T, E> {
+    fn into_answer(self) -> Answer {
+        match self {
+            Ok(result) => Answer::Solved(result.to_string()),
+            Err(e) => Answer::Failed(render_error(e.into().as_ref())),
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartOutput<Optional> for Result<Option<T>, E> {
+    fn into_answer(self) -> Answer {
+        match self {
+            Ok(result) => result.into_answer(),
+            Err(e) => Answer::Failed(render_error(e.into().as_ref())),
+        }
+    }
+}
+
+/// Renders an error followed by its chain of sources.
+#[must_use]
+pub fn render_error(error: &dyn Error) -> String {
+    let mut rendered = error.to_string();
+    let mut source = error.source();
+
+    while let Some(e) = source {
+        rendered.push_str(&format!("\n  caused by: {e}"));
+        source = e.source();
+    }
+
+    rendered
+}
+
+/// Runs a solution part and prints its result. Returns the answer so callers can react to failures.
+pub fn run_part<I: Clone, R: PartOutput<K>, K>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+) -> Answer {
     let part_str = format!("Part {part}");
 
+    // discard spans that were recorded outside of this part.
+    let _ = trace::take();
+
     let (result, duration, samples) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
+    if env::args().any(|x| x == "--trace") {
+        print_trace(samples);
+    }
+
+    if let Answer::Solved(result) = &result {
         submit_result(result, day, part);
     }
+
+    result
+}
+
+/// Reads the input a solution runs against: `data/inputs/<day>.txt` by default,
+/// a file or stdin with `--input <path>|-`, or an example with `--example [name]`.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    read_custom_input(day).unwrap_or_else(|| read_file("inputs", day))
+}
+
+/// Like [`read_input`], but defaults to an input that was embedded at compile time.
+/// The input is empty if it did not exist when the binary was built.
+#[must_use]
+pub fn read_embedded_input(day: Day, embedded: &str) -> String {
+    read_custom_input(day).unwrap_or_else(|| {
+        if embedded.is_empty() {
+            eprintln!("No input was embedded for day {day}. Download it and build again, or pass `--input <path>`.");
+            process::exit(1);
+        }
+        embedded.to_string()
+    })
+}
+
+fn read_custom_input(day: Day) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+
+    if let Some(index) = args.iter().position(|x| x == "--input") {
+        let Some(path) = args.get(index + 1) else {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --input <path>|-");
+            process::exit(1);
+        };
+
+        let input = if path == "-" {
+            let mut input = String::new();
+            stdin().read_to_string(&mut input).map(|_| input)
+        } else {
+            fs::read_to_string(path)
+        };
+
+        return Some(input.unwrap_or_else(|e| {
+            eprintln!("Failed to read input from \"{path}\": {e}");
+            process::exit(1);
+        }));
+    }
+
+    if let Some(index) = args.iter().position(|x| x == "--example") {
+        return Some(match args.get(index + 1).filter(|x| !x.starts_with("--")) {
+            Some(name) => {
+                let path = config::get()
+                    .data_dir("examples")
+                    .join(format!("{day}-{name}.txt"));
+                crypt::read_to_string(&path).unwrap_or_else(|e| {
+                    eprintln!("Failed to read example {path:?}: {e}");
+                    process::exit(1);
+                })
+            }
+            None => read_file("examples", day),
+        });
+    }
+
+    None
+}
+
+/// Returns `true` unless the solution was invoked with `--part <n>` for the other part.
+#[must_use]
+pub fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+    let Some(index) = args.iter().position(|x| x == "--part") else {
+        return true;
+    };
+
+    match args.get(index + 1).and_then(|x| x.parse::<u8>().ok()) {
+        Some(selected @ (1 | 2)) => selected == part,
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 1|2");
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns `true` if the solution runs against another input than `data/inputs/<day>.txt`.
+fn is_custom_input() -> bool {
+    env::args().any(|x| x == "--input" || x == "--example")
+}
+
+/// Configures the global rayon thread pool if the solution was invoked with `--threads <n>`.
+/// This has to happen before the first parallel iterator is used.
+pub fn configure_threads() {
+    if let Some(threads) = get_thread_arg() {
+        if rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .is_err()
+        {
+            eprintln!("Failed to configure thread pool with {threads} threads.");
+        }
+    }
+}
+
+fn get_thread_arg() -> Option<usize> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--threads")?;
+
+    match args.get(index + 1).and_then(|x| x.parse().ok()) {
+        Some(threads) if threads > 0 => Some(threads),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --threads 4");
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns the maximum scale if the solution was invoked with `--stress <scale>`.
+#[must_use]
+pub fn get_stress_scale() -> Option<u32> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--stress")?;
+
+    match args.get(index + 1).and_then(|x| x.parse().ok()) {
+        Some(scale) if scale > 0 => Some(scale),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo stress 5 --scale 8");
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns `true` if the solution was invoked with `--speedup`.
+#[must_use]
+pub fn is_speedup_run() -> bool {
+    env::args().any(|x| x == "--speedup")
+}
+
+/// Runs a part on a single thread and on `--threads` (or all available) threads and prints the parallel speedup.
+pub fn run_speedup<I: Clone + Send + Sync, R: PartOutput<K>, K>(
+    func: impl Fn(I) -> R + Sync,
+    input: I,
+    part: u8,
+) {
+    let threads = get_thread_arg().unwrap_or_else(|| {
+        std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+    });
+
+    let run_with = |num_threads: usize| {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .expect("could not build thread pool");
+        // a speedup from single runs is mostly noise, so both thread counts are benched.
+        pool.install(|| {
+            let timer = Instant::now();
+            let result = func(input.clone()).into_answer();
+            let base_time = timer.elapsed();
+
+            let (duration, samples) = if result.is_failed() {
+                (base_time, 1)
+            } else {
+                bench(&func, input.clone(), &base_time)
+            };
+            (result, duration, samples)
+        })
+    };
+
+    let (result, single, single_samples) = run_with(1);
+    let (_, multi, multi_samples) = run_with(threads);
+    let result = format_answer(&result);
+
+    print!("\r");
+    println!(
+        "Part {part}: {ANSI_BOLD}{result}{ANSI_RESET} (1 thread: {single:.1?} @ {single_samples} samples, {threads} threads: {multi:.1?} @ {multi_samples} samples, speedup x{:.2})",
+        single.as_secs_f64() / multi.as_secs_f64().max(1e-9)
+    );
+}
+
+/// Runs the visualization of a day once, outside of the timed parts.
+pub fn run_visualization(visualize: Option<fn(&str)>, input: &str) {
+    match visualize {
+        Some(visualize) => visualize(input),
+        None => eprintln!(
+            "This day has no visualization. Register one with `solution!(<day>, visualize: <function>)`."
+        ),
+    }
+}
+
+/// A named implementation of a solution part.
+pub type Variant<'a, R> = (&'a str, &'a dyn Fn(&str) -> R);
+
+/// Returns `true` if the solution was invoked with `--compare`.
+#[must_use]
+pub fn is_compare_run() -> bool {
+    env::args().any(|x| x == "--compare")
+}
+
+/// Runs every implementation of a part against the same input and prints a side-by-side table.
+/// The first entry is treated as the reference, speedups are relative to it.
+/// Returns `false` if any implementation disagrees with the reference.
+pub fn compare_part<R: PartOutput<K>, K>(variants: &[Variant<R>], input: &str, part: u8) -> bool {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    let name_width = variants
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let mut reference: Option<(Answer, Duration)> = None;
+    let mut agree = true;
+
+    for (name, func) in variants {
+        let (answer, duration, samples) = run_timed(func, input, |_| {});
+        let result = format_answer(&answer);
+
+        let (matches, speedup) = match &reference {
+            Some((expected, base)) => (
+                *expected == answer && !answer.is_failed(),
+                format!(
+                    " x{:.2}",
+                    base.as_secs_f64() / duration.as_secs_f64().max(1e-9)
+                ),
+            ),
+            None => {
+                let matches = !answer.is_failed();
+                reference = Some((answer, duration));
+                (matches, String::new())
+            }
+        };
+
+        agree &= matches;
+        let status = if matches { "✔" } else { "✖ differs" };
+
+        print!("\r");
+        println!(
+            "  {name:<name_width$}  {ANSI_BOLD}{result}{ANSI_RESET}{}{speedup}  {status}",
+            format_duration(&duration, samples)
+        );
+    }
+
+    agree
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///
+/// Failed parts are not benched.
+fn run_timed<I: Clone, R: PartOutput<K>, K>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    hook: impl Fn(&Answer),
+) -> (Answer, Duration, u128) {
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
 
+    let result = result.into_answer();
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if std::env::args().any(|x| x == "--time") && !result.is_failed() {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -45,19 +373,20 @@ fn run_timed<I: Clone, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Benches `func` for the configured time budget and returns its average duration and the number of samples.
+pub(crate) fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let settings = &config::get().bench;
+    let bench_iterations = (settings.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(settings.min_samples, settings.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -88,15 +417,49 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({duration:.1?} @ {samples} samples) [{} threads]",
+            rayon::current_num_threads()
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_trace(samples: u128) {
+    if !trace::is_enabled() {
+        eprintln!("Span tracing is disabled. Run with \"cargo solve <day> --trace\" to enable it.");
+        return;
+    }
+
+    let records = trace::take();
+
+    if records.is_empty() {
+        println!("{ANSI_ITALIC}No spans recorded.{ANSI_RESET}");
+    } else if samples > 1 {
+        // the initial run is recorded as well as every bench iteration.
+        println!(
+            "{ANSI_ITALIC}Spans aggregated over {} runs:{ANSI_RESET}",
+            samples + 1
+        );
+        print!("{}", trace::render(&records));
+    } else {
+        print!("{}", trace::render(&records));
+    }
+}
+
+/// Formats an answer for single line output.
+fn format_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Solved(result) => result.clone(),
+        Answer::Unsolved => "✖".into(),
+        Answer::Failed(_) => "⚠ error".into(),
+    }
+}
+
+fn print_result(result: &Answer, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Answer::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -116,7 +479,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Answer::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -124,14 +487,22 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Answer::Failed(error) => {
+            if is_intermediate_result {
+                print!("{part}: ⚠");
+            } else {
+                print!("\r");
+                println!("{part}: ⚠ error: {error}");
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+fn submit_result(
+    result: &str,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
@@ -157,11 +528,64 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if is_custom_input() {
+        eprintln!("Refusing to submit an answer that was not computed from the puzzle input.");
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, result))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare_part, Answer, PartOutput, Variant};
+    use std::fmt::{self, Display};
+
+    fn count_lines(input: &str) -> Option<usize> {
+        Some(input.lines().count())
+    }
+
+    fn count_newlines(input: &str) -> Option<usize> {
+        Some(input.matches('\n').count())
+    }
+
+    struct Position(i32, i32);
+
+    impl Display for Position {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{},{}", self.0, self.1)
+        }
+    }
+
+    #[test]
+    fn accepts_any_display_answer() {
+        let solved: Result<Position, std::fmt::Error> = Ok(Position(3, 4));
+        assert_eq!(solved.into_answer(), Answer::Solved("3,4".into()));
+
+        let unsolved: Result<Option<Position>, std::fmt::Error> = Ok(None);
+        assert_eq!(unsolved.into_answer(), Answer::Unsolved);
+
+        let failed: Result<u32, std::fmt::Error> = Err(std::fmt::Error);
+        assert!(failed.into_answer().is_failed());
+    }
+
+    #[test]
+    fn compares_variants() {
+        let input = "a\nb\nc\n";
+        let matching: [Variant<Option<usize>>; 2] =
+            [("lines", &count_lines), ("newlines", &count_newlines)];
+        assert!(compare_part(&matching, input, 1));
+
+        // without a trailing newline, the variants disagree.
+        assert!(!compare_part(&matching, "a\nb\nc", 1));
+
+        let unsolved: [Variant<Option<usize>>; 2] = [("lines", &count_lines), ("todo", &|_| None)];
+        assert!(!compare_part(&unsolved, input, 1));
+    }
 }
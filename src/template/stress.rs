@@ -0,0 +1,149 @@
+/// Module that times solution parts on scaled-up inputs to estimate their complexity.
+use std::io::{stdout, Write};
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::template::runner::{bench, Answer, PartOutput};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Generates an input that is `scale` times larger than the given input.
+pub type Generator = fn(&str, u32) -> String;
+
+/// Returns the scales a part is timed at: powers of two up to `max`, and `max` itself.
+#[must_use]
+pub fn scales(max: u32) -> Vec<u32> {
+    let mut scales: Vec<u32> = (0..)
+        .map_while(|exp| 1u32.checked_shl(exp))
+        .take_while(|s| *s < max)
+        .collect();
+    scales.push(max.max(1));
+    scales
+}
+
+/// Estimates the exponent `e` of `time ~ scale^e` with a least-squares fit in log-log space.
+#[must_use]
+pub fn growth_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(s, t)| *s > 0.0 && *t > 0.0)
+        .map(|(s, t)| (s.ln(), t.ln()))
+        .collect();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    if variance == 0.0 {
+        return None;
+    }
+
+    Some(covariance / variance)
+}
+
+/// Runs a part at every scale up to `max_scale`. Exits if the day has no generator.
+pub fn run<R: PartOutput<K>, K>(
+    func: impl Fn(&str) -> R,
+    generate: Option<Generator>,
+    input: &str,
+    max_scale: u32,
+    part: u8,
+) {
+    let Some(generate) = generate else {
+        eprintln!("This day has no stress generator. Register one with `solution!(<day>, stress: <generator>)`.");
+        process::exit(1);
+    };
+
+    run_part(func, generate, input, max_scale, part);
+}
+
+/// Times a part on inputs produced by `generate` at every scale up to `max_scale` and prints the growth exponent.
+pub fn run_part<R: PartOutput<K>, K>(
+    func: impl Fn(&str) -> R,
+    generate: Generator,
+    input: &str,
+    max_scale: u32,
+    part: u8,
+) {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    let mut points = vec![];
+
+    for scale in scales(max_scale) {
+        print!("  x{scale:<6} {ANSI_ITALIC}generating{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        let scaled = if scale == 1 {
+            input.to_string()
+        } else {
+            generate(input, scale)
+        };
+
+        print!("\r  x{scale:<6} {ANSI_ITALIC}running{ANSI_RESET}   ");
+        let _ = stdout().flush();
+
+        let timer = Instant::now();
+        let result = func(&scaled).into_answer();
+        let base_time = timer.elapsed();
+
+        let (elapsed, samples) = if result.is_failed() {
+            (base_time, 1)
+        } else {
+            bench(&func, scaled.as_str(), &base_time)
+        };
+
+        let result = match result {
+            Answer::Solved(result) => result,
+            Answer::Unsolved => "✖".into(),
+            Answer::Failed(error) => format!("⚠ error: {error}"),
+        };
+        println!(
+            "\r  x{scale:<6} {elapsed:>10.1?} @ {samples:<5} ({} bytes) {result}",
+            scaled.len()
+        );
+
+        points.push((
+            f64::from(scale),
+            elapsed.max(Duration::from_nanos(1)).as_secs_f64(),
+        ));
+    }
+
+    match growth_exponent(&points) {
+        Some(exponent) => println!("  growth: ~O(n^{exponent:.2})"),
+        None => println!("  growth: {ANSI_ITALIC}needs at least two scales{ANSI_RESET}"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{growth_exponent, scales};
+
+    #[test]
+    fn scales_are_powers_of_two() {
+        assert_eq!(scales(1), vec![1]);
+        assert_eq!(scales(8), vec![1, 2, 4, 8]);
+        assert_eq!(scales(10), vec![1, 2, 4, 8, 10]);
+        assert_eq!(scales(u32::MAX).len(), 33);
+    }
+
+    #[test]
+    fn estimates_exponent() {
+        let linear = growth_exponent(&[(1.0, 3.0), (2.0, 6.0), (4.0, 12.0)]).unwrap();
+        assert!((linear - 1.0).abs() < 1e-9);
+
+        let quadratic = growth_exponent(&[(1.0, 1.0), (2.0, 4.0), (8.0, 64.0)]).unwrap();
+        assert!((quadratic - 2.0).abs() < 1e-9);
+
+        assert_eq!(growth_exponent(&[(1.0, 1.0)]), None);
+    }
+}
@@ -0,0 +1,304 @@
+//! Lightweight span tracing for solutions.
+//!
+//! Wrap the phases of a part in named spans:
+//!
+//! ```
+//! fn part_one(input: &str) -> Option<u32> {
+//!     let data = {
+//!         let _span = advent_of_code::span!("parse");
+//!         parse(input)
+//!     };
+//!     let _span = advent_of_code::span!("solve");
+//!     solve(data)
+//! }
+//! ```
+//!
+//! Spans are only recorded when the crate is built with the `trace` feature (`cargo solve <day> --trace`).
+//! Without it, [`Span`] is a zero-sized type and the spans compile to nothing.
+use std::fmt::Write as _;
+use std::time::Duration;
+
+/// Aggregated timings of all spans that were entered with the same path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Names of the enclosing spans, ending with the span itself.
+    pub path: Vec<&'static str>,
+    pub calls: u64,
+    pub total: Duration,
+}
+
+/// Opens a named span that is closed when the returned guard is dropped.
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        $crate::trace::Span::enter($name)
+    };
+}
+
+#[cfg(feature = "trace")]
+mod recorder {
+    use super::Record;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
+    use std::thread::{self, ThreadId};
+    use std::time::{Duration, Instant};
+
+    thread_local! {
+        static STACK: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+    }
+
+    /// The thread that opened the outermost span and its open spans. Spans opened on other
+    /// threads without a parent of their own, e.g. in rayon workers, are nested below them.
+    static ROOT: Mutex<Option<(ThreadId, Vec<&'static str>)>> = Mutex::new(None);
+
+    /// Counts entered spans, so records can be listed in the order they were first entered.
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+    /// Records by path, with the sequence number of their first entry.
+    type Records = HashMap<Vec<&'static str>, (Record, u64)>;
+
+    static RECORDS: Mutex<Option<Records>> = Mutex::new(None);
+
+    /// Guard of an open span.
+    pub struct Span {
+        path: Vec<&'static str>,
+        start: Instant,
+        sequence: u64,
+        /// The span was opened without a parent on this thread and inherited the root's spans.
+        inherited: bool,
+        /// The span was opened on the root thread.
+        root: bool,
+    }
+
+    impl Span {
+        pub fn enter(name: &'static str) -> Self {
+            let thread = thread::current().id();
+            let mut root = ROOT.lock().unwrap();
+
+            let (path, inherited) = STACK.with(|stack| {
+                let mut stack = stack.borrow_mut();
+                let mut inherited = false;
+                if stack.is_empty() {
+                    if let Some((id, path)) = root.as_ref() {
+                        if *id != thread && !path.is_empty() {
+                            stack.extend(path);
+                            inherited = true;
+                        }
+                    }
+                }
+                stack.push(name);
+                (stack.clone(), inherited)
+            });
+
+            let is_root = match root.as_mut() {
+                _ if inherited => false,
+                Some((id, path)) if *id == thread => {
+                    path.push(name);
+                    true
+                }
+                Some((_, path)) if !path.is_empty() => false,
+                _ => {
+                    *root = Some((thread, vec![name]));
+                    true
+                }
+            };
+
+            Self {
+                path,
+                start: Instant::now(),
+                sequence: SEQUENCE.fetch_add(1, Ordering::Relaxed),
+                inherited,
+                root: is_root,
+            }
+        }
+    }
+
+    impl Drop for Span {
+        fn drop(&mut self) {
+            let elapsed = self.start.elapsed();
+
+            STACK.with(|stack| {
+                let mut stack = stack.borrow_mut();
+                if self.inherited {
+                    stack.clear();
+                } else {
+                    stack.pop();
+                }
+            });
+
+            if self.root {
+                if let Some((_, path)) = ROOT.lock().unwrap().as_mut() {
+                    path.pop();
+                }
+            }
+
+            let mut records = RECORDS.lock().unwrap();
+            let (record, sequence) = records
+                .get_or_insert_with(HashMap::new)
+                .entry(self.path.clone())
+                .or_insert_with(|| {
+                    let record = Record {
+                        path: self.path.clone(),
+                        calls: 0,
+                        total: Duration::ZERO,
+                    };
+                    (record, self.sequence)
+                });
+            record.calls += 1;
+            record.total += elapsed;
+            *sequence = (*sequence).min(self.sequence);
+        }
+    }
+
+    pub fn take() -> Vec<Record> {
+        let records = RECORDS.lock().unwrap().take().unwrap_or_default();
+
+        // order by the first entry of every ancestor, so children stay below their parent.
+        let order = |path: &[&'static str]| -> Vec<u64> {
+            (1..=path.len())
+                .map(|len| records.get(&path[..len]).map_or(u64::MAX, |(_, s)| *s))
+                .collect()
+        };
+        let mut sorted: Vec<(Vec<u64>, Record)> = records
+            .values()
+            .map(|(record, _)| (order(&record.path), record.clone()))
+            .collect();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+        sorted.into_iter().map(|(_, record)| record).collect()
+    }
+}
+
+#[cfg(not(feature = "trace"))]
+mod recorder {
+    use super::Record;
+
+    /// Guard of an open span. Tracing is disabled, so this does nothing.
+    pub struct Span;
+
+    impl Span {
+        #[inline(always)]
+        pub fn enter(_name: &'static str) -> Self {
+            Span
+        }
+    }
+
+    pub fn take() -> Vec<Record> {
+        vec![]
+    }
+}
+
+pub use recorder::Span;
+
+/// Returns `true` if the crate was built with span tracing enabled.
+#[must_use]
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "trace")
+}
+
+/// Returns all spans recorded since the last call and clears them.
+/// Records are listed in the order they were first entered, every span below its parent.
+#[must_use]
+pub fn take() -> Vec<Record> {
+    recorder::take()
+}
+
+/// Renders records as an indented timing tree.
+#[must_use]
+pub fn render(records: &[Record]) -> String {
+    let name_width = records
+        .iter()
+        .map(|r| r.path.len() * 2 + r.path.last().map_or(0, |n| n.len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+
+    for record in records {
+        let indent = "  ".repeat(record.path.len());
+        let name = format!("{indent}{}", record.path.last().unwrap_or(&""));
+        let calls = if record.calls == 1 { "call" } else { "calls" };
+        let _ = writeln!(
+            out,
+            "{name:<name_width$}  {:.1?} ({} {calls})",
+            record.total, record.calls
+        );
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Record};
+    use std::time::Duration;
+
+    #[test]
+    fn renders_nested_spans() {
+        let records = vec![
+            Record {
+                path: vec!["parse"],
+                calls: 1,
+                total: Duration::from_micros(1500),
+            },
+            Record {
+                path: vec!["parse", "line"],
+                calls: 20,
+                total: Duration::from_micros(1200),
+            },
+            Record {
+                path: vec!["solve"],
+                calls: 1,
+                total: Duration::from_millis(3),
+            },
+        ];
+
+        let expected = [
+            "  parse   1.5ms (1 call)",
+            "    line  1.2ms (20 calls)",
+            "  solve   3.0ms (1 call)",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(render(&records), expected);
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn records_nested_spans() {
+        {
+            let _outer = crate::span!("outer");
+            for _ in 0..3 {
+                let _inner = crate::span!("inner");
+            }
+            // spans on other threads without a parent of their own are nested below the open spans.
+            std::thread::scope(|scope| {
+                scope.spawn(|| {
+                    let _worker = crate::span!("worker");
+                });
+            });
+        }
+        {
+            let _parse = crate::span!("parse");
+        }
+        {
+            let _locate = crate::span!("locate");
+        }
+
+        let records = super::take();
+        let paths: Vec<_> = records.iter().map(|r| r.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec!["outer"],
+                vec!["outer", "inner"],
+                vec!["outer", "worker"],
+                vec!["parse"],
+                vec!["locate"],
+            ]
+        );
+        assert_eq!(records[1].calls, 3);
+    }
+}
@@ -0,0 +1,323 @@
+//! Renders grids with per-cell colors to the terminal and to PPM/PNG images.
+//!
+//! Solutions register a visualization with the `solution!` macro. It runs once after the parts
+//! when the solution is run with `--visualize` (`cargo solve <day> --visualize`):
+//!
+//! ```
+//! advent_of_code::solution!(3, visualize: visualize_grid);
+//!
+//! fn visualize_grid(input: &str) {
+//!     let frame = Grid::parse(input, Cell::new).map(|pos, c| c.fg(Rgb::GREEN));
+//!     visualize::show(&frame);
+//! }
+//! ```
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fs, io, thread};
+
+use crate::grid::Grid;
+use crate::template::{config, ANSI_RESET};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const GRAY: Rgb = Rgb(110, 110, 110);
+    pub const WHITE: Rgb = Rgb(230, 230, 230);
+    pub const RED: Rgb = Rgb(220, 60, 60);
+    pub const GREEN: Rgb = Rgb(80, 200, 90);
+    pub const BLUE: Rgb = Rgb(70, 130, 230);
+    pub const YELLOW: Rgb = Rgb(240, 200, 50);
+    pub const MAGENTA: Rgb = Rgb(210, 80, 200);
+}
+
+/// A character with optional foreground and background colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+}
+
+impl Cell {
+    #[must_use]
+    pub fn new(ch: char) -> Self {
+        Self {
+            ch,
+            fg: None,
+            bg: None,
+        }
+    }
+
+    #[must_use]
+    pub fn fg(mut self, color: Rgb) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    #[must_use]
+    pub fn bg(mut self, color: Rgb) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// The color of the cell in images: the background if set, then the foreground.
+    fn pixel(&self) -> Rgb {
+        match (self.bg, self.fg) {
+            (Some(bg), _) => bg,
+            (None, Some(fg)) => fg,
+            (None, None) if self.ch.is_whitespace() || self.ch == '.' => Rgb::BLACK,
+            (None, None) => Rgb::GRAY,
+        }
+    }
+}
+
+/// A single rendered state of a grid.
+pub type Frame = Grid<Cell>;
+
+/// Returns `true` if the solution was invoked with `--visualize`.
+#[must_use]
+pub fn is_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| env::args().any(|x| x == "--visualize"))
+}
+
+/// Renders a frame with ANSI true color escape codes.
+#[must_use]
+pub fn to_ansi(frame: &Frame) -> String {
+    let mut out = String::new();
+
+    for y in 0..frame.height() {
+        for cell in frame.row(y) {
+            if let Some(Rgb(r, g, b)) = cell.fg {
+                let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+            }
+            if let Some(Rgb(r, g, b)) = cell.bg {
+                let _ = write!(out, "\x1b[48;2;{r};{g};{b}m");
+            }
+            out.push(cell.ch);
+            if cell.fg.is_some() || cell.bg.is_some() {
+                out.push_str(ANSI_RESET);
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Prints a frame to the terminal.
+pub fn show(frame: &Frame) {
+    print!("{}", to_ansi(frame));
+}
+
+fn pixels(frame: &Frame, scale: usize) -> (usize, usize, Vec<u8>) {
+    let width = frame.width() * scale;
+    let height = frame.height() * scale;
+    let mut data = Vec::with_capacity(width * height * 3);
+
+    for y in 0..height {
+        for x in 0..width {
+            let Rgb(r, g, b) = frame
+                .get((x / scale, y / scale))
+                .map_or(Rgb::BLACK, Cell::pixel);
+            data.extend([r, g, b]);
+        }
+    }
+
+    (width, height, data)
+}
+
+/// Encodes a frame as a binary PPM image, every cell is drawn as a `scale`×`scale` square.
+#[must_use]
+pub fn to_ppm(frame: &Frame, scale: usize) -> Vec<u8> {
+    let (width, height, data) = pixels(frame, scale.max(1));
+    let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+    out.extend(data);
+    out
+}
+
+/// Encodes a frame as a PNG image, every cell is drawn as a `scale`×`scale` square.
+/// Image data is stored uncompressed, which keeps the encoder free of dependencies.
+#[must_use]
+pub fn to_png(frame: &Frame, scale: usize) -> Vec<u8> {
+    let (width, height, data) = pixels(frame, scale.max(1));
+
+    // every scanline starts with filter type 0 (none).
+    let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+    for line in data.chunks(width * 3).take(height) {
+        raw.push(0);
+        raw.extend_from_slice(line);
+    }
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = vec![];
+    header.extend(u32::try_from(width).unwrap_or(u32::MAX).to_be_bytes());
+    header.extend(u32::try_from(height).unwrap_or(u32::MAX).to_be_bytes());
+    // bit depth 8, color type 2 (RGB), default compression, filter and interlace.
+    header.extend([8, 2, 0, 0, 0]);
+
+    png_chunk(&mut out, b"IHDR", &header);
+    png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    png_chunk(&mut out, b"IEND", &[]);
+
+    out
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend(u32::try_from(data.len()).unwrap_or(u32::MAX).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let is_final = u8::from(blocks.peek().is_none());
+        let len = u16::try_from(block.len()).unwrap_or(u16::MAX);
+        out.push(is_final);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + u32::from(*byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Collects frames of a simulation for replay in the terminal or export as images.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    #[must_use]
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Replays all frames in place in the terminal.
+    pub fn replay(&self, delay: Duration) {
+        for (i, frame) in self.frames.iter().enumerate() {
+            // move the cursor home and clear the screen before every frame.
+            print!("\x1b[H\x1b[2J{}", to_ansi(frame));
+            println!("frame {}/{}", i + 1, self.frames.len());
+            thread::sleep(delay);
+        }
+    }
+
+    /// Writes all frames as `target/visualize/<name>-<frame>.png` and returns the folder.
+    pub fn save_png(&self, name: &str, scale: usize) -> Result<PathBuf, io::Error> {
+        self.save(name, "png", |frame| to_png(frame, scale))
+    }
+
+    /// Writes all frames as `target/visualize/<name>-<frame>.ppm` and returns the folder.
+    pub fn save_ppm(&self, name: &str, scale: usize) -> Result<PathBuf, io::Error> {
+        self.save(name, "ppm", |frame| to_ppm(frame, scale))
+    }
+
+    fn save(
+        &self,
+        name: &str,
+        extension: &str,
+        encode: impl Fn(&Frame) -> Vec<u8>,
+    ) -> Result<PathBuf, io::Error> {
+        let dir = config::target_dir().join("visualize");
+        fs::create_dir_all(&dir)?;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            fs::write(
+                dir.join(format!("{name}-{i:04}.{extension}")),
+                encode(frame),
+            )?;
+        }
+
+        Ok(dir)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{adler32, crc32, to_ansi, to_png, to_ppm, Cell, Frame, Rgb};
+
+    fn frame() -> Frame {
+        Frame::parse("#.\n.#", |c| match c {
+            '#' => Cell::new(c).fg(Rgb::RED),
+            c => Cell::new(c),
+        })
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn renders_ansi() {
+        let out = to_ansi(&frame());
+        assert_eq!(
+            out,
+            "\x1b[38;2;220;60;60m#\x1b[0m.\n.\x1b[38;2;220;60;60m#\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn encodes_ppm() {
+        let ppm = to_ppm(&frame(), 2);
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+        assert_eq!(&ppm[11..14], &[220, 60, 60]);
+    }
+
+    #[test]
+    fn encodes_png() {
+        let png = to_png(&frame(), 1);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+    }
+}